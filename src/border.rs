@@ -0,0 +1,764 @@
+use std::borrow::Borrow;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use fltk::group::Group;
+use fltk::prelude::{GroupExt, WidgetBase};
+use fltk::widget::Widget;
+
+use crate::{IntoWidget, LayoutElement, LayoutNotifier, Padding, Size, WrapperFactory};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderRegion {
+    North,
+    South,
+    East,
+    West,
+    Center,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderCorners {
+    NorthSouth,
+    EastWest,
+}
+
+pub struct BorderLayout<G: GroupExt + Clone = Group> {
+    props: BorderLayoutProperties<G>,
+    cache: RefCell<BorderLayoutCache>,
+}
+
+pub struct BorderLayoutBuilder<
+    G: GroupExt + Clone = Group,
+    F: Borrow<WrapperFactory> = WrapperFactory,
+> {
+    props: BorderLayoutProperties<G>,
+    factory: F,
+}
+
+struct BorderLayoutProperties<G: GroupExt + Clone> {
+    group: G,
+    padding: Padding,
+    spacing: i32,
+    corners: BorderCorners,
+    regions: [Option<Rc<dyn LayoutElement>>; 5],
+    auto_refresh: bool,
+    notifier: Rc<LayoutNotifier>,
+}
+
+struct BorderLayoutCache {
+    min_sizes: [Option<Size>; 5],
+    min_size: Size,
+    max_size: Size,
+    height_for_width: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Extent {
+    start: i32,
+    size: i32,
+}
+
+impl<G: GroupExt + Clone> LayoutElement for BorderLayout<G> {
+    fn min_size(&self) -> Size {
+        self.cache().min_size
+    }
+
+    fn max_size(&self) -> Size {
+        self.cache().max_size
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        self.cache().height_for_width
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        let cache = self.cache();
+        if !cache.height_for_width {
+            return cache.min_size.height;
+        }
+
+        let horz = Extent {
+            start: 0,
+            size: width - (self.props.padding.left + self.props.padding.right),
+        };
+        let sizes = self.sizes_for_width(&cache, horz);
+        border_min_size(self.props.corners, &sizes, self.props.spacing).height
+            + self.props.padding.top
+            + self.props.padding.bottom
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        for region in self.props.regions.iter().flatten() {
+            region.invalidate();
+        }
+        self.props.notifier.take_stale();
+        *self.cache.borrow_mut() = BorderLayoutCache::new(&self.props);
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.props.notifier))
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.props.group.clone().resize(x, y, width, height);
+        self.layout_children();
+    }
+}
+
+impl BorderLayout {
+    pub fn builder() -> BorderLayoutBuilder<Group, WrapperFactory> {
+        BorderLayoutBuilder::new(Group::default_fill())
+    }
+
+    pub fn builder_with_factory<F: Borrow<WrapperFactory>>(
+        factory: F,
+    ) -> BorderLayoutBuilder<Group, F> {
+        BorderLayoutBuilder::with_factory(Group::default_fill(), factory)
+    }
+}
+
+impl<G: GroupExt + Clone> BorderLayout<G> {
+    pub fn group(&self) -> G {
+        self.props.group.clone()
+    }
+
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
+    pub fn layout_children(&self) {
+        if self.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+
+        let cache = self.cache();
+        let horz = Extent {
+            start: self.props.group.x() + self.props.padding.left,
+            size: self.props.group.width() - (self.props.padding.left + self.props.padding.right),
+        };
+        let vert = Extent {
+            start: self.props.group.y() + self.props.padding.top,
+            size: self.props.group.height() - (self.props.padding.top + self.props.padding.bottom),
+        };
+        let sizes = self.sizes_for_width(&cache, horz);
+        let areas = region_areas(self.props.corners, horz, vert, &sizes, self.props.spacing);
+
+        for (element, (horz, vert)) in self.props.regions.iter().zip(areas.iter()) {
+            if let Some(element) = element {
+                element.layout(horz.start, vert.start, horz.size, vert.size);
+            }
+        }
+    }
+
+    fn sizes_for_width(&self, cache: &BorderLayoutCache, horz: Extent) -> [Option<Size>; 5] {
+        let mut sizes = cache.min_sizes;
+        if !cache.height_for_width {
+            return sizes;
+        }
+
+        let vert = Extent { start: 0, size: 0 };
+        let areas = region_areas(self.props.corners, horz, vert, &sizes, self.props.spacing);
+        for ((size, element), (area_horz, _)) in sizes
+            .iter_mut()
+            .zip(self.props.regions.iter())
+            .zip(areas.iter())
+        {
+            if let (Some(size), Some(element)) = (size.as_mut(), element) {
+                if element.has_height_for_width() {
+                    size.height = element.min_height_for_width(area_horz.size);
+                }
+            }
+        }
+        sizes
+    }
+
+    fn new(props: BorderLayoutProperties<G>) -> Self {
+        if props.auto_refresh {
+            props.notifier.enable_auto_refresh();
+        }
+        let cache = RefCell::new(BorderLayoutCache::new(&props));
+        Self { props, cache }
+    }
+
+    fn cache(&self) -> Ref<'_, BorderLayoutCache> {
+        if self.props.notifier.take_stale() {
+            *self.cache.borrow_mut() = BorderLayoutCache::new(&self.props);
+        }
+        self.cache.borrow()
+    }
+}
+
+impl BorderLayoutCache {
+    fn new<G: GroupExt + Clone>(props: &BorderLayoutProperties<G>) -> Self {
+        let mut min_sizes = [None; 5];
+        for (size, region) in min_sizes.iter_mut().zip(props.regions.iter()) {
+            *size = region.as_ref().map(|element| element.min_size());
+        }
+
+        let mut max_sizes = [None; 5];
+        for (size, region) in max_sizes.iter_mut().zip(props.regions.iter()) {
+            *size = region.as_ref().map(|element| element.max_size());
+        }
+        let height_for_width = props
+            .regions
+            .iter()
+            .flatten()
+            .any(|element| element.has_height_for_width());
+
+        let padding_width = props.padding.left + props.padding.right;
+        let padding_height = props.padding.top + props.padding.bottom;
+        let mut min_size = border_min_size(props.corners, &min_sizes, props.spacing);
+        min_size.width += padding_width;
+        min_size.height += padding_height;
+        let mut max_size = border_max_size(props.corners, &min_sizes, &max_sizes, props.spacing);
+        max_size.width =
+            std::cmp::max(max_size.width.saturating_add(padding_width), min_size.width);
+        max_size.height = std::cmp::max(
+            max_size.height.saturating_add(padding_height),
+            min_size.height,
+        );
+
+        Self {
+            min_sizes,
+            min_size,
+            max_size,
+            height_for_width,
+        }
+    }
+}
+
+impl<G: GroupExt + Clone> BorderLayoutBuilder<G> {
+    pub fn new(group: G) -> Self {
+        Self::with_factory(group, WrapperFactory::with_defaults())
+    }
+}
+
+impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> BorderLayoutBuilder<G, F> {
+    pub fn with_factory(group: G, factory: F) -> Self {
+        Self {
+            props: BorderLayoutProperties {
+                group,
+                padding: Default::default(),
+                spacing: 0,
+                corners: BorderCorners::NorthSouth,
+                regions: Default::default(),
+                auto_refresh: false,
+                notifier: LayoutNotifier::new(),
+            },
+            factory,
+        }
+    }
+
+    pub fn with_spacing(mut self, spacing: i32) -> Self {
+        self.props.spacing = std::cmp::max(0, spacing);
+        self
+    }
+
+    pub fn with_corners(mut self, corners: BorderCorners) -> Self {
+        self.props.corners = corners;
+        self
+    }
+
+    pub fn with_left_padding(mut self, padding: i32) -> Self {
+        self.props.padding.left = padding;
+        self
+    }
+
+    pub fn with_top_padding(mut self, padding: i32) -> Self {
+        self.props.padding.top = padding;
+        self
+    }
+
+    pub fn with_right_padding(mut self, padding: i32) -> Self {
+        self.props.padding.right = padding;
+        self
+    }
+
+    pub fn with_bottom_padding(mut self, padding: i32) -> Self {
+        self.props.padding.bottom = padding;
+        self
+    }
+
+    pub fn with_padding(mut self, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        self.props.padding = Padding {
+            left,
+            top,
+            right,
+            bottom,
+        };
+        self
+    }
+
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
+    pub fn add<E: LayoutElement + 'static>(&mut self, region: BorderRegion, element: E) {
+        self.add_shared(region, Rc::new(element));
+    }
+
+    pub fn add_shared(&mut self, region: BorderRegion, element: Rc<dyn LayoutElement>) {
+        self.props.notifier.adopt(&*element);
+        self.props.regions[region as usize] = Some(element);
+    }
+
+    pub fn wrap<W: IntoWidget + 'static>(&mut self, region: BorderRegion, widget: W) -> W {
+        let element = self.factory.borrow().wrap(widget.clone());
+        self.add_shared(region, element);
+        widget
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        region: BorderRegion,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.add_shared(region, Rc::new(wrapper(widget.clone())));
+        widget
+    }
+
+    pub fn end(self) -> BorderLayout<G> {
+        self.props.group.end();
+        BorderLayout::new(self.props)
+    }
+}
+
+fn split_extent(
+    extent: Extent,
+    first: Option<i32>,
+    has_middle: bool,
+    last: Option<i32>,
+    spacing: i32,
+) -> (Extent, Extent, Extent) {
+    let first_size = first.unwrap_or_default();
+    let last_size = last.unwrap_or_default();
+    let first_gap = if first.is_some() && (has_middle || last.is_some()) { spacing } else { 0 };
+    let last_gap = if last.is_some() && has_middle { spacing } else { 0 };
+
+    let middle_start = extent.start + first_size + first_gap;
+    let middle_size = std::cmp::max(
+        0,
+        extent.size - (first_size + first_gap + last_gap + last_size),
+    );
+    (
+        Extent {
+            start: extent.start,
+            size: first_size,
+        },
+        Extent {
+            start: middle_start,
+            size: middle_size,
+        },
+        Extent {
+            start: extent.start + extent.size - last_size,
+            size: last_size,
+        },
+    )
+}
+
+fn stack_size(sizes: [Option<i32>; 3], spacing: i32) -> i32 {
+    let present = sizes.iter().flatten().count() as i32;
+    sizes.iter().flatten().sum::<i32>() + std::cmp::max(0, present - 1) * spacing
+}
+
+fn region_areas(
+    corners: BorderCorners,
+    horz: Extent,
+    vert: Extent,
+    sizes: &[Option<Size>; 5],
+    spacing: i32,
+) -> [(Extent, Extent); 5] {
+    let north_height = sizes[BorderRegion::North as usize].map(|size| size.height);
+    let south_height = sizes[BorderRegion::South as usize].map(|size| size.height);
+    let west_width = sizes[BorderRegion::West as usize].map(|size| size.width);
+    let east_width = sizes[BorderRegion::East as usize].map(|size| size.width);
+    let has_center = sizes[BorderRegion::Center as usize].is_some();
+
+    let (north, south, east, west, center) = match corners {
+        BorderCorners::NorthSouth => {
+            let has_middle = west_width.is_some() || has_center || east_width.is_some();
+            let (north, middle, south) =
+                split_extent(vert, north_height, has_middle, south_height, spacing);
+            let (west, center, east) =
+                split_extent(horz, west_width, has_center, east_width, spacing);
+            (
+                (horz, north),
+                (horz, south),
+                (east, middle),
+                (west, middle),
+                (center, middle),
+            )
+        }
+        BorderCorners::EastWest => {
+            let has_middle = north_height.is_some() || has_center || south_height.is_some();
+            let (west, middle, east) =
+                split_extent(horz, west_width, has_middle, east_width, spacing);
+            let (north, center, south) =
+                split_extent(vert, north_height, has_center, south_height, spacing);
+            (
+                (middle, north),
+                (middle, south),
+                (east, vert),
+                (west, vert),
+                (middle, center),
+            )
+        }
+    };
+    [north, south, east, west, center]
+}
+
+fn border_min_size(corners: BorderCorners, sizes: &[Option<Size>; 5], spacing: i32) -> Size {
+    let north = sizes[BorderRegion::North as usize];
+    let south = sizes[BorderRegion::South as usize];
+    let east = sizes[BorderRegion::East as usize];
+    let west = sizes[BorderRegion::West as usize];
+    let center = sizes[BorderRegion::Center as usize];
+
+    match corners {
+        BorderCorners::NorthSouth => {
+            let middle = Size {
+                width: stack_size(
+                    [west, center, east].map(|size| size.map(|size| size.width)),
+                    spacing,
+                ),
+                height: [west, center, east]
+                    .iter()
+                    .flatten()
+                    .map(|size| size.height)
+                    .max()
+                    .unwrap_or_default(),
+            };
+            let has_middle = west.is_some() || center.is_some() || east.is_some();
+            Size {
+                width: [north, Some(middle), south]
+                    .iter()
+                    .flatten()
+                    .map(|size| size.width)
+                    .max()
+                    .unwrap_or_default(),
+                height: stack_size(
+                    [
+                        north.map(|size| size.height),
+                        has_middle.then_some(middle.height),
+                        south.map(|size| size.height),
+                    ],
+                    spacing,
+                ),
+            }
+        }
+        BorderCorners::EastWest => {
+            let middle = Size {
+                width: [north, center, south]
+                    .iter()
+                    .flatten()
+                    .map(|size| size.width)
+                    .max()
+                    .unwrap_or_default(),
+                height: stack_size(
+                    [north, center, south].map(|size| size.map(|size| size.height)),
+                    spacing,
+                ),
+            };
+            let has_middle = north.is_some() || center.is_some() || south.is_some();
+            Size {
+                width: stack_size(
+                    [
+                        west.map(|size| size.width),
+                        has_middle.then_some(middle.width),
+                        east.map(|size| size.width),
+                    ],
+                    spacing,
+                ),
+                height: [west, Some(middle), east]
+                    .iter()
+                    .flatten()
+                    .map(|size| size.height)
+                    .max()
+                    .unwrap_or_default(),
+            }
+        }
+    }
+}
+
+fn border_max_size(
+    corners: BorderCorners,
+    min_sizes: &[Option<Size>; 5],
+    max_sizes: &[Option<Size>; 5],
+    spacing: i32,
+) -> Size {
+    let min_width = |region: BorderRegion| min_sizes[region as usize].map(|size| size.width);
+    let min_height = |region: BorderRegion| min_sizes[region as usize].map(|size| size.height);
+    let max_width = |region: BorderRegion| max_sizes[region as usize].map(|size| size.width);
+    let max_height = |region: BorderRegion| max_sizes[region as usize].map(|size| size.height);
+    let narrowest = |regions: &[BorderRegion]| regions.iter().filter_map(|&r| max_width(r)).min();
+    let shortest = |regions: &[BorderRegion]| regions.iter().filter_map(|&r| max_height(r)).min();
+
+    match corners {
+        BorderCorners::NorthSouth => {
+            let middle = [BorderRegion::West, BorderRegion::Center, BorderRegion::East];
+            let middle_width = max_width(BorderRegion::Center).map(|center| {
+                stack_max(
+                    min_width(BorderRegion::West),
+                    center,
+                    min_width(BorderRegion::East),
+                    spacing,
+                )
+            });
+            let middle_height = shortest(&middle).map(|middle| {
+                stack_max(
+                    min_height(BorderRegion::North),
+                    middle,
+                    min_height(BorderRegion::South),
+                    spacing,
+                )
+            });
+            Size {
+                width: [
+                    max_width(BorderRegion::North),
+                    middle_width,
+                    max_width(BorderRegion::South),
+                ]
+                .iter()
+                .flatten()
+                .copied()
+                .min()
+                .unwrap_or(Size::UNBOUNDED.width),
+                height: middle_height.unwrap_or(Size::UNBOUNDED.height),
+            }
+        }
+        BorderCorners::EastWest => {
+            let middle = [
+                BorderRegion::North,
+                BorderRegion::Center,
+                BorderRegion::South,
+            ];
+            let middle_height = max_height(BorderRegion::Center).map(|center| {
+                stack_max(
+                    min_height(BorderRegion::North),
+                    center,
+                    min_height(BorderRegion::South),
+                    spacing,
+                )
+            });
+            let middle_width = narrowest(&middle).map(|middle| {
+                stack_max(
+                    min_width(BorderRegion::West),
+                    middle,
+                    min_width(BorderRegion::East),
+                    spacing,
+                )
+            });
+            Size {
+                width: middle_width.unwrap_or(Size::UNBOUNDED.width),
+                height: [
+                    max_height(BorderRegion::West),
+                    middle_height,
+                    max_height(BorderRegion::East),
+                ]
+                .iter()
+                .flatten()
+                .copied()
+                .min()
+                .unwrap_or(Size::UNBOUNDED.height),
+            }
+        }
+    }
+}
+
+fn stack_max(first: Option<i32>, middle: i32, last: Option<i32>, spacing: i32) -> i32 {
+    middle.saturating_add(stack_size([first, Some(0), last], spacing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extent(start: i32, size: i32) -> Extent {
+        Extent { start, size }
+    }
+
+    fn size(width: i32, height: i32) -> Option<Size> {
+        Some(Size { width, height })
+    }
+
+    // North, South, East, West, Center
+    fn all_regions() -> [Option<Size>; 5] {
+        [
+            size(30, 10),
+            size(20, 5),
+            size(6, 30),
+            size(8, 40),
+            size(50, 20),
+        ]
+    }
+
+    #[test]
+    fn split_extent_reserves_spacing() {
+        assert_eq!(
+            split_extent(extent(0, 100), Some(10), true, Some(5), 3),
+            (extent(0, 10), extent(13, 79), extent(95, 5))
+        );
+        assert_eq!(
+            split_extent(extent(0, 100), Some(10), false, Some(5), 3),
+            (extent(0, 10), extent(13, 82), extent(95, 5))
+        );
+        assert_eq!(
+            split_extent(extent(0, 100), None, true, Some(5), 3),
+            (extent(0, 0), extent(0, 92), extent(95, 5))
+        );
+        assert_eq!(
+            split_extent(extent(0, 10), Some(8), true, Some(8), 3),
+            (extent(0, 8), extent(11, 0), extent(2, 8))
+        );
+    }
+
+    #[test]
+    fn stack_size_counts_present_gaps() {
+        assert_eq!(stack_size([Some(10), Some(20), Some(5)], 3), 41);
+        assert_eq!(stack_size([Some(10), None, Some(5)], 3), 18);
+        assert_eq!(stack_size([None, Some(20), None], 3), 20);
+        assert_eq!(stack_size([None, None, None], 3), 0);
+    }
+
+    #[test]
+    fn north_south_corners() {
+        let areas = region_areas(
+            BorderCorners::NorthSouth,
+            extent(0, 100),
+            extent(0, 80),
+            &all_regions(),
+            2,
+        );
+        assert_eq!(
+            areas,
+            [
+                (extent(0, 100), extent(0, 10)),
+                (extent(0, 100), extent(75, 5)),
+                (extent(94, 6), extent(12, 61)),
+                (extent(0, 8), extent(12, 61)),
+                (extent(10, 82), extent(12, 61)),
+            ]
+        );
+        assert_eq!(
+            border_min_size(BorderCorners::NorthSouth, &all_regions(), 2),
+            Size {
+                width: 68,
+                height: 59
+            }
+        );
+    }
+
+    #[test]
+    fn east_west_corners() {
+        let areas = region_areas(
+            BorderCorners::EastWest,
+            extent(0, 100),
+            extent(0, 80),
+            &all_regions(),
+            2,
+        );
+        assert_eq!(
+            areas,
+            [
+                (extent(10, 82), extent(0, 10)),
+                (extent(10, 82), extent(75, 5)),
+                (extent(94, 6), extent(0, 80)),
+                (extent(0, 8), extent(0, 80)),
+                (extent(10, 82), extent(12, 61)),
+            ]
+        );
+        assert_eq!(
+            border_min_size(BorderCorners::EastWest, &all_regions(), 2),
+            Size {
+                width: 68,
+                height: 40
+            }
+        );
+    }
+
+    #[test]
+    fn missing_regions_leave_no_gaps() {
+        let mut sizes = all_regions();
+        sizes[BorderRegion::West as usize] = None;
+        sizes[BorderRegion::Center as usize] = None;
+        let areas = region_areas(
+            BorderCorners::NorthSouth,
+            extent(0, 100),
+            extent(0, 80),
+            &sizes,
+            2,
+        );
+        assert_eq!(
+            areas[BorderRegion::East as usize],
+            (extent(94, 6), extent(12, 61))
+        );
+        assert_eq!(
+            border_min_size(BorderCorners::NorthSouth, &sizes, 2),
+            Size {
+                width: 30,
+                height: 49
+            }
+        );
+
+        let mut sizes = all_regions();
+        sizes[BorderRegion::North as usize] = None;
+        sizes[BorderRegion::Center as usize] = None;
+        let areas = region_areas(
+            BorderCorners::EastWest,
+            extent(0, 100),
+            extent(0, 80),
+            &sizes,
+            2,
+        );
+        assert_eq!(
+            areas[BorderRegion::South as usize],
+            (extent(10, 82), extent(75, 5))
+        );
+        assert_eq!(
+            border_min_size(BorderCorners::EastWest, &sizes, 2),
+            Size {
+                width: 38,
+                height: 40
+            }
+        );
+
+        assert_eq!(
+            border_min_size(BorderCorners::NorthSouth, &[None; 5], 2),
+            Size::default()
+        );
+    }
+
+    #[test]
+    fn max_size_follows_center() {
+        let min_sizes = all_regions();
+        let mut max_sizes = [Some(Size::UNBOUNDED); 5];
+        max_sizes[BorderRegion::Center as usize] = size(60, 25);
+
+        let expected = Size {
+            width: 78,
+            height: 44,
+        };
+        for corners in [BorderCorners::NorthSouth, BorderCorners::EastWest] {
+            assert_eq!(
+                border_max_size(corners, &min_sizes, &max_sizes, 2),
+                expected
+            );
+        }
+
+        let max_sizes = [Some(Size::UNBOUNDED); 5];
+        assert_eq!(
+            border_max_size(BorderCorners::NorthSouth, &min_sizes, &max_sizes, 2),
+            Size::UNBOUNDED
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::ops::{Deref, DerefMut};
+
+use fltk::browser::BrowserScrollbar;
+use fltk::enums::Font;
+use fltk::prelude::*;
+use fltk::widget::Widget;
+
+use super::{LayoutElement, LayoutWidgetWrapper, Size};
+
+pub(crate) const DEFAULT_VISIBLE_ROWS: i32 = 5;
+pub(crate) const DEFAULT_VISIBLE_CHARS: i32 = 20;
+
+pub struct BrowserElement<B: BrowserExt + Clone> {
+    widget: B,
+    visible_rows: i32,
+    visible_chars: i32,
+    line_spacing: i32,
+}
+
+impl<B: BrowserExt + Clone> LayoutWidgetWrapper<B> for BrowserElement<B> {
+    fn wrap(widget: B) -> Self {
+        Self {
+            widget,
+            visible_rows: DEFAULT_VISIBLE_ROWS,
+            visible_chars: DEFAULT_VISIBLE_CHARS,
+            line_spacing: 0,
+        }
+    }
+}
+
+impl<B: BrowserExt + Clone> BrowserElement<B> {
+    pub fn with_visible_rows(mut self, rows: i32) -> Self {
+        self.visible_rows = std::cmp::max(1, rows);
+        self
+    }
+
+    pub fn with_visible_chars(mut self, chars: i32) -> Self {
+        self.visible_chars = std::cmp::max(1, chars);
+        self
+    }
+
+    pub fn with_line_spacing(mut self, spacing: i32) -> Self {
+        self.line_spacing = std::cmp::max(0, spacing);
+        self
+    }
+}
+
+impl<B: BrowserExt + Clone> LayoutElement for BrowserElement<B> {
+    fn min_size(&self) -> Size {
+        // fltk-rs exposes neither the text font nor the line spacing of browsers, so measure
+        // with Fl_Browser's default text font and the spacing hint given to the element.
+        fltk::draw::set_font(Font::Helvetica, self.widget.text_size());
+        let line_height = std::cmp::max(2, fltk::draw::height()) + self.line_spacing;
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        let scrollbars = self.widget.has_scrollbar() as i32;
+        let scrollbar = scrollbar_size(self.widget.scrollbar_size());
+        let vert_scrollbar =
+            if scrollbars & BrowserScrollbar::Vertical as i32 != 0 { scrollbar } else { 0 };
+        let horz_scrollbar =
+            if scrollbars & BrowserScrollbar::Horizontal as i32 != 0 { scrollbar } else { 0 };
+        Size {
+            width: self.visible_chars * char_width() + frame_width + vert_scrollbar,
+            height: self.visible_rows * line_height + frame_height + horz_scrollbar,
+        }
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl<B: BrowserExt + Clone> Deref for BrowserElement<B> {
+    type Target = B;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<B: BrowserExt + Clone> DerefMut for BrowserElement<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+pub(crate) fn char_width() -> i32 {
+    fltk::draw::width("0").ceil() as i32
+}
+
+pub(crate) fn scrollbar_size(widget_size: i32) -> i32 {
+    if widget_size > 0 {
+        widget_size
+    } else {
+        fltk::app::scrollbar_size()
+    }
+}
@@ -4,9 +4,12 @@ use std::ops::{Deref, DerefMut};
 use fltk::enums::FrameType;
 use fltk::menu::MenuButton;
 use fltk::prelude::*;
+use fltk::widget::Widget;
 
 use super::{LayoutElement, LayoutWidgetWrapper, Size};
 
+const INDICATOR_GAP: i32 = 2;
+
 pub struct ButtonElement<B: ButtonExt + Clone> {
     widget: B,
 }
@@ -22,6 +25,22 @@ impl<B: ButtonExt + Clone> LayoutElement for ButtonElement<B> {
         button_min_size(&self.widget, self.widget.down_frame())
     }
 
+    fn baseline(&self) -> Option<i32> {
+        self.baseline_for_height(self.min_size().height)
+    }
+
+    fn baseline_for_height(&self, height: i32) -> Option<i32> {
+        Some(button_baseline(
+            &self.widget,
+            self.widget.down_frame(),
+            height,
+        ))
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.widget.clone().resize(x, y, width, height)
     }
@@ -55,11 +74,161 @@ impl LayoutElement for MenuButtonElement {
         button_min_size(&self.widget, self.widget.down_frame())
     }
 
+    fn baseline(&self) -> Option<i32> {
+        self.baseline_for_height(self.min_size().height)
+    }
+
+    fn baseline_for_height(&self, height: i32) -> Option<i32> {
+        Some(button_baseline(
+            &self.widget,
+            self.widget.down_frame(),
+            height,
+        ))
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height)
+    }
+}
+
+pub struct CheckButtonElement<B: ButtonExt + Clone> {
+    widget: B,
+}
+
+impl<B: ButtonExt + Clone> LayoutWidgetWrapper<B> for CheckButtonElement<B> {
+    fn wrap(widget: B) -> Self {
+        Self { widget }
+    }
+}
+
+impl<B: ButtonExt + Clone> LayoutElement for CheckButtonElement<B> {
+    fn min_size(&self) -> Size {
+        let indicator_size = self.widget.label_size();
+        indicator_button_min_size(&self.widget, indicator_size, indicator_size)
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        self.baseline_for_height(self.min_size().height)
+    }
+
+    fn baseline_for_height(&self, height: i32) -> Option<i32> {
+        Some(indicator_button_baseline(&self.widget, height))
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.widget.clone().resize(x, y, width, height)
     }
 }
 
+impl<B: ButtonExt + Clone> Deref for CheckButtonElement<B> {
+    type Target = B;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<B: ButtonExt + Clone> DerefMut for CheckButtonElement<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+pub struct LightButtonElement<B: ButtonExt + Clone> {
+    widget: B,
+}
+
+impl<B: ButtonExt + Clone> LayoutWidgetWrapper<B> for LightButtonElement<B> {
+    fn wrap(widget: B) -> Self {
+        Self { widget }
+    }
+}
+
+impl<B: ButtonExt + Clone> LayoutElement for LightButtonElement<B> {
+    fn min_size(&self) -> Size {
+        let indicator_size = self.widget.label_size();
+        let indicator_width = if self.widget.down_frame() == FrameType::NoBox {
+            indicator_size / 2 + 1
+        } else {
+            indicator_size
+        };
+        indicator_button_min_size(&self.widget, indicator_width, indicator_size)
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        self.baseline_for_height(self.min_size().height)
+    }
+
+    fn baseline_for_height(&self, height: i32) -> Option<i32> {
+        Some(indicator_button_baseline(&self.widget, height))
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height)
+    }
+}
+
+impl<B: ButtonExt + Clone> Deref for LightButtonElement<B> {
+    type Target = B;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<B: ButtonExt + Clone> DerefMut for LightButtonElement<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+fn indicator_button_min_size<B: WidgetExt>(
+    button: &B,
+    indicator_width: i32,
+    indicator_height: i32,
+) -> Size {
+    let (label_width, label_height) = button.measure_label();
+    let frame = button.frame();
+    let frame_width = frame.dx() + frame.dw();
+    let frame_height = frame.dy() + frame.dh();
+    let indicator_start = frame.dx() + INDICATOR_GAP;
+
+    Size {
+        width: indicator_start + indicator_width + INDICATOR_GAP + label_width + frame_width,
+        height: max(indicator_height, label_height) + frame_height,
+    }
+}
+
+fn indicator_button_baseline<B: WidgetExt>(button: &B, height: i32) -> i32 {
+    let (_, label_height) = button.measure_label();
+    let frame = button.frame();
+    let content_height = height - (frame.dy() + frame.dh());
+    let label_start = max(0, content_height - label_height) / 2;
+    fltk::draw::set_font(button.label_font(), button.label_size());
+    frame.dy() + label_start + fltk::draw::height() - fltk::draw::descent()
+}
+
+fn button_baseline<B: WidgetExt>(button: &B, down_frame: FrameType, height: i32) -> i32 {
+    let (_, label_height) = button.measure_label();
+    let up_frame = button.frame();
+    let frame_dy = max(up_frame.dy(), down_frame.dy());
+    let frame_dh = max(up_frame.dh(), down_frame.dh());
+    let content_height = height - (frame_dy + frame_dh);
+    let label_start = max(0, content_height - label_height) / 2;
+    fltk::draw::set_font(button.label_font(), button.label_size());
+    frame_dy + label_start + fltk::draw::height() - fltk::draw::descent()
+}
+
 fn button_min_size<B: WidgetExt>(button: &B, down_frame: FrameType) -> Size {
     let (label_width, label_height) = button.measure_label();
     let up_frame = button.frame();
@@ -104,6 +273,18 @@ impl<B: ButtonExt + Clone> LayoutElement for FramelessButtonElement<B> {
         }
     }
 
+    fn baseline(&self) -> Option<i32> {
+        let up_frame = self.widget.frame();
+        let down_frame = self.widget.down_frame();
+        let frame_dy = max(up_frame.dy(), down_frame.dy());
+        fltk::draw::set_font(self.widget.label_font(), self.widget.label_size());
+        Some(frame_dy + fltk::draw::height() - fltk::draw::descent())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.widget.clone().resize(x, y, width, height)
     }
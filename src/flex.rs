@@ -0,0 +1,747 @@
+use std::borrow::{Borrow, Cow};
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use fltk::group::Group;
+use fltk::prelude::{GroupExt, WidgetBase};
+use fltk::widget::Widget;
+
+use crate::grid::{
+    baseline_block, calc_baseline_bounds, calc_stripe_bounds, calc_widget_bounds,
+    collect_stretch_stripes, sort_stretch_stripes, span_size, CellAlign, Stripe, StripeProperties,
+};
+use crate::{IntoWidget, LayoutElement, LayoutNotifier, Padding, Size, WrapperFactory};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlexDirection {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlexJustify {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+}
+
+pub struct Flex<G: GroupExt + Clone = Group> {
+    props: FlexProperties<G>,
+    cache: RefCell<FlexCache>,
+}
+
+pub struct FlexBuilder<G: GroupExt + Clone = Group, F: Borrow<WrapperFactory> = WrapperFactory> {
+    props: FlexProperties<G>,
+    factory: F,
+}
+
+pub struct FlexChildBuilder<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> {
+    owner: &'l mut FlexBuilder<G, F>,
+    stretch: u8,
+    cross_align: CellAlign,
+}
+
+struct FlexProperties<G: GroupExt + Clone> {
+    group: G,
+    direction: FlexDirection,
+    padding: Padding,
+    spacing: i32,
+    justify: FlexJustify,
+    cross_align: CellAlign,
+    children: Vec<FlexChild>,
+    auto_refresh: bool,
+    notifier: Rc<LayoutNotifier>,
+}
+
+struct FlexChild {
+    element: Rc<dyn LayoutElement>,
+    stretch: u8,
+    cross_align: CellAlign,
+}
+
+struct FlexCache {
+    stripes: Vec<Stripe>,
+    groups: Vec<StripeProperties>,
+    stretch_stripes: Vec<usize>,
+    cross_sizes: Vec<(i32, i32)>,
+    baselines: Vec<Option<i32>>,
+    min_size: Size,
+    max_size: Size,
+    height_for_width: bool,
+}
+
+impl<G: GroupExt + Clone> LayoutElement for Flex<G> {
+    fn min_size(&self) -> Size {
+        self.cache().min_size
+    }
+
+    fn max_size(&self) -> Size {
+        self.cache().max_size
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        self.cache().height_for_width
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        let cache = self.cache();
+        if !cache.height_for_width {
+            return cache.min_size.height;
+        }
+
+        let width = width - (self.props.padding.left + self.props.padding.right);
+        let height = match self.props.direction {
+            FlexDirection::Horizontal => {
+                let bounds = calc_main_bounds(
+                    &cache.stripes,
+                    &cache.groups,
+                    &cache.stretch_stripes,
+                    self.props.spacing,
+                    width,
+                );
+                self.props
+                    .children
+                    .iter()
+                    .zip(cache.cross_sizes.iter())
+                    .zip(bounds.iter())
+                    .map(|((child, &(min_height, max_height)), &(_, child_width))| {
+                        child_height_for_width(child, child_width, min_height, max_height)
+                    })
+                    .max()
+                    .unwrap_or_default()
+            }
+            FlexDirection::Vertical => {
+                let groups = self.main_groups(&cache, width);
+                span_size(&cache.stripes, &groups, &[], self.props.spacing)
+            }
+        };
+        height + self.props.padding.top + self.props.padding.bottom
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        for child in self.props.children.iter() {
+            child.element.invalidate();
+        }
+        self.props.notifier.take_stale();
+        *self.cache.borrow_mut() = FlexCache::new(&self.props);
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.props.notifier))
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.props.group.clone().resize(x, y, width, height);
+        self.layout_children();
+    }
+}
+
+impl Flex {
+    pub fn builder(direction: FlexDirection) -> FlexBuilder<Group, WrapperFactory> {
+        FlexBuilder::new(Group::default_fill(), direction)
+    }
+
+    pub fn builder_with_factory<F: Borrow<WrapperFactory>>(
+        direction: FlexDirection,
+        factory: F,
+    ) -> FlexBuilder<Group, F> {
+        FlexBuilder::with_factory(Group::default_fill(), direction, factory)
+    }
+}
+
+impl<G: GroupExt + Clone> Flex<G> {
+    pub fn group(&self) -> G {
+        self.props.group.clone()
+    }
+
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
+    pub fn layout_children(&self) {
+        if self.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+
+        let x = self.props.group.x() + self.props.padding.left;
+        let y = self.props.group.y() + self.props.padding.top;
+        let width = self.props.group.width() - (self.props.padding.left + self.props.padding.right);
+        let height =
+            self.props.group.height() - (self.props.padding.top + self.props.padding.bottom);
+
+        let (main_start, main_size, cross_start, cross_size) = match self.props.direction {
+            FlexDirection::Horizontal => (x, width, y, height),
+            FlexDirection::Vertical => (y, height, x, width),
+        };
+
+        let cache = self.cache();
+        let groups = self.main_groups(&cache, cross_size);
+        let mut bounds = calc_main_bounds(
+            &cache.stripes,
+            &groups,
+            &cache.stretch_stripes,
+            self.props.spacing,
+            main_size,
+        );
+        justify_bounds(&mut bounds, self.props.justify, main_size);
+
+        let min_crosses: Vec<i32> = self
+            .props
+            .children
+            .iter()
+            .zip(bounds.iter())
+            .zip(cache.cross_sizes.iter())
+            .map(|((child, &(_, child_size)), &(min_cross, max_cross))| {
+                match self.props.direction {
+                    FlexDirection::Horizontal => {
+                        child_height_for_width(child, child_size, min_cross, max_cross)
+                    }
+                    FlexDirection::Vertical => min_cross,
+                }
+            })
+            .collect();
+        let baselines: Vec<Option<i32>> = self
+            .props
+            .children
+            .iter()
+            .zip(cache.baselines.iter())
+            .zip(min_crosses.iter())
+            .map(|((child, baseline), &min_cross)| {
+                baseline.and_then(|_| {
+                    child
+                        .element
+                        .baseline_for_height(std::cmp::min(cross_size, min_cross))
+                })
+            })
+            .collect();
+        let baseline = baseline_block(
+            baselines
+                .iter()
+                .zip(min_crosses.iter())
+                .filter_map(|(baseline, &min_cross)| Some(((*baseline)?, min_cross))),
+        );
+
+        for (
+            (child, &(child_start, child_size)),
+            ((&(_, max_cross), &min_cross), &child_baseline),
+        ) in self.props.children.iter().zip(bounds.iter()).zip(
+            cache
+                .cross_sizes
+                .iter()
+                .zip(min_crosses.iter())
+                .zip(baselines.iter()),
+        ) {
+            let child_start = main_start + child_start;
+            let (child_cross_start, child_cross_size) = match (child_baseline, baseline) {
+                (Some(child_baseline), Some(block)) => calc_baseline_bounds(
+                    cross_start,
+                    cross_size,
+                    block,
+                    child_baseline,
+                    std::cmp::min(cross_size, min_cross),
+                    0,
+                    0,
+                ),
+                _ => calc_widget_bounds(
+                    cross_start,
+                    cross_size,
+                    min_cross,
+                    std::cmp::max(min_cross, max_cross),
+                    0,
+                    0,
+                    cross_align(self.props.direction, child.cross_align),
+                ),
+            };
+            match self.props.direction {
+                FlexDirection::Horizontal => child.element.layout(
+                    child_start,
+                    child_cross_start,
+                    child_size,
+                    child_cross_size,
+                ),
+                FlexDirection::Vertical => child.element.layout(
+                    child_cross_start,
+                    child_start,
+                    child_cross_size,
+                    child_size,
+                ),
+            }
+        }
+    }
+
+    fn main_groups<'c>(&self, cache: &'c FlexCache, width: i32) -> Cow<'c, [StripeProperties]> {
+        if !cache.height_for_width || (self.props.direction == FlexDirection::Horizontal) {
+            return Cow::Borrowed(&cache.groups);
+        }
+
+        let mut groups = cache.groups.clone();
+        for ((child, group), &(min_width, max_width)) in self
+            .props
+            .children
+            .iter()
+            .zip(groups.iter_mut())
+            .zip(cache.cross_sizes.iter())
+        {
+            let align = cross_align(self.props.direction, child.cross_align);
+            let (_, child_width) = calc_widget_bounds(0, width, min_width, max_width, 0, 0, align);
+            group.min_size =
+                child_height_for_width(child, child_width, group.min_size, group.max_size);
+            group.max_size = std::cmp::max(group.max_size, group.min_size);
+        }
+        Cow::Owned(groups)
+    }
+
+    fn new(props: FlexProperties<G>) -> Self {
+        if props.auto_refresh {
+            props.notifier.enable_auto_refresh();
+        }
+        let cache = RefCell::new(FlexCache::new(&props));
+        Self { props, cache }
+    }
+
+    fn cache(&self) -> Ref<'_, FlexCache> {
+        if self.props.notifier.take_stale() {
+            *self.cache.borrow_mut() = FlexCache::new(&self.props);
+        }
+        self.cache.borrow()
+    }
+}
+
+impl FlexCache {
+    fn new<G: GroupExt + Clone>(props: &FlexProperties<G>) -> Self {
+        let mut stripes = Vec::with_capacity(props.children.len());
+        let mut groups = Vec::with_capacity(props.children.len());
+        let mut cross_sizes = Vec::with_capacity(props.children.len());
+        let mut baselines = Vec::with_capacity(props.children.len());
+        let mut height_for_width = false;
+
+        for (idx, child) in props.children.iter().enumerate() {
+            let min_size = child.element.min_size();
+            let max_size = child.element.max_size();
+            let (min_main, max_main, min_cross, max_cross) = match props.direction {
+                FlexDirection::Horizontal => (
+                    min_size.width,
+                    max_size.width,
+                    min_size.height,
+                    max_size.height,
+                ),
+                FlexDirection::Vertical => (
+                    min_size.height,
+                    max_size.height,
+                    min_size.width,
+                    max_size.width,
+                ),
+            };
+            let (stripe, group) = main_stripe(idx, child.stretch, min_main, max_main);
+            stripes.push(stripe);
+            groups.push(group);
+            cross_sizes.push((min_cross, std::cmp::max(min_cross, max_cross)));
+            baselines.push(match cross_align(props.direction, child.cross_align) {
+                CellAlign::Baseline => child.element.baseline(),
+                _ => None,
+            });
+            height_for_width |= child.element.has_height_for_width();
+        }
+
+        let mut stretch_stripes = collect_stretch_stripes(&stripes, &groups);
+        sort_stretch_stripes(&stripes, &groups, &mut stretch_stripes);
+
+        let padding_width = props.padding.left + props.padding.right;
+        let padding_height = props.padding.top + props.padding.bottom;
+        let main_min = span_size(&stripes, &groups, &[], props.spacing);
+        let main_max = if props.justify == FlexJustify::Start {
+            let spacing = std::cmp::max(0, stripes.len() as i32 - 1) * props.spacing;
+            stripes
+                .iter()
+                .map(|stripe| {
+                    let group = &groups[stripe.group_idx];
+                    if group.is_stretch() {
+                        group.max_size
+                    } else {
+                        group.min_size
+                    }
+                })
+                .fold(spacing, i32::saturating_add)
+        } else {
+            i32::MAX
+        };
+        let baseline_height = baseline_block(
+            baselines
+                .iter()
+                .zip(cross_sizes.iter())
+                .filter_map(|(baseline, &(min_cross, _))| Some(((*baseline)?, min_cross))),
+        )
+        .map(|(ascent, descent)| ascent + descent)
+        .unwrap_or_default();
+        let cross_min = cross_sizes
+            .iter()
+            .map(|&(min, _)| min)
+            .fold(baseline_height, std::cmp::max);
+        let cross_max = cross_sizes
+            .iter()
+            .map(|&(_, max)| max)
+            .max()
+            .unwrap_or_default();
+
+        let (min_size, max_size) = match props.direction {
+            FlexDirection::Horizontal => (
+                Size {
+                    width: main_min + padding_width,
+                    height: cross_min + padding_height,
+                },
+                Size {
+                    width: main_max.saturating_add(padding_width),
+                    height: cross_max.saturating_add(padding_height),
+                },
+            ),
+            FlexDirection::Vertical => (
+                Size {
+                    width: cross_min + padding_width,
+                    height: main_min + padding_height,
+                },
+                Size {
+                    width: cross_max.saturating_add(padding_width),
+                    height: main_max.saturating_add(padding_height),
+                },
+            ),
+        };
+
+        Self {
+            stripes,
+            groups,
+            stretch_stripes,
+            cross_sizes,
+            baselines,
+            min_size,
+            max_size: Size {
+                width: std::cmp::max(max_size.width, min_size.width),
+                height: std::cmp::max(max_size.height, min_size.height),
+            },
+            height_for_width,
+        }
+    }
+}
+
+impl<G: GroupExt + Clone> FlexBuilder<G> {
+    pub fn new(group: G, direction: FlexDirection) -> Self {
+        Self::with_factory(group, direction, WrapperFactory::with_defaults())
+    }
+}
+
+impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> FlexBuilder<G, F> {
+    pub fn with_factory(group: G, direction: FlexDirection, factory: F) -> Self {
+        Self {
+            props: FlexProperties {
+                group,
+                direction,
+                padding: Default::default(),
+                spacing: 0,
+                justify: FlexJustify::Start,
+                cross_align: CellAlign::Stretch,
+                children: Vec::new(),
+                auto_refresh: false,
+                notifier: LayoutNotifier::new(),
+            },
+            factory,
+        }
+    }
+
+    pub fn with_spacing(mut self, spacing: i32) -> Self {
+        self.props.spacing = std::cmp::max(0, spacing);
+        self
+    }
+
+    pub fn with_left_padding(mut self, padding: i32) -> Self {
+        self.props.padding.left = padding;
+        self
+    }
+
+    pub fn with_top_padding(mut self, padding: i32) -> Self {
+        self.props.padding.top = padding;
+        self
+    }
+
+    pub fn with_right_padding(mut self, padding: i32) -> Self {
+        self.props.padding.right = padding;
+        self
+    }
+
+    pub fn with_bottom_padding(mut self, padding: i32) -> Self {
+        self.props.padding.bottom = padding;
+        self
+    }
+
+    pub fn with_padding(mut self, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        self.props.padding = Padding {
+            left,
+            top,
+            right,
+            bottom,
+        };
+        self
+    }
+
+    pub fn with_justify(mut self, justify: FlexJustify) -> Self {
+        self.props.justify = justify;
+        self
+    }
+
+    pub fn with_cross_align(mut self, align: CellAlign) -> Self {
+        self.props.cross_align = align;
+        self
+    }
+
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
+    pub fn child(&mut self) -> FlexChildBuilder<'_, G, F> {
+        let cross_align = self.props.cross_align;
+        FlexChildBuilder {
+            owner: self,
+            stretch: 0,
+            cross_align,
+        }
+    }
+
+    pub fn add<E: LayoutElement + 'static>(&mut self, element: E) {
+        self.child().add(element);
+    }
+
+    pub fn add_shared(&mut self, element: Rc<dyn LayoutElement>) {
+        self.child().add_shared(element);
+    }
+
+    pub fn wrap<W: IntoWidget + 'static>(&mut self, widget: W) -> W {
+        self.child().wrap(widget)
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.child().wrap_with(widget, wrapper)
+    }
+
+    pub fn end(self) -> Flex<G> {
+        self.props.group.end();
+        Flex::new(self.props)
+    }
+}
+
+impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> FlexChildBuilder<'l, G, F> {
+    pub fn with_stretch(mut self, stretch: u8) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
+    pub fn with_cross_align(mut self, align: CellAlign) -> Self {
+        self.cross_align = align;
+        self
+    }
+
+    pub fn add<E: LayoutElement + 'static>(self, element: E) {
+        self.add_shared(Rc::new(element));
+    }
+
+    pub fn add_shared(self, element: Rc<dyn LayoutElement>) {
+        self.owner.props.notifier.adopt(&*element);
+        self.owner.props.children.push(FlexChild {
+            element,
+            stretch: self.stretch,
+            cross_align: self.cross_align,
+        });
+    }
+
+    pub fn wrap<W: IntoWidget + 'static>(self, widget: W) -> W {
+        let element = self.owner.factory.borrow().wrap(widget.clone());
+        self.add_shared(element);
+        widget
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.add_shared(Rc::new(wrapper(widget.clone())));
+        widget
+    }
+}
+
+fn cross_align(direction: FlexDirection, align: CellAlign) -> CellAlign {
+    match (direction, align) {
+        (FlexDirection::Vertical, CellAlign::Baseline) => CellAlign::Start,
+        _ => align,
+    }
+}
+
+fn main_stripe(
+    idx: usize,
+    stretch: u8,
+    min_size: i32,
+    max_size: i32,
+) -> (Stripe, StripeProperties) {
+    let stripe = Stripe {
+        cells: Vec::new(),
+        group_idx: idx,
+    };
+    let group = StripeProperties {
+        stretch: f64::from(stretch),
+        percentage: None,
+        min_size,
+        max_size: std::cmp::max(min_size, max_size),
+        shrink_priority: 0,
+        collapsible: false,
+    };
+    (stripe, group)
+}
+
+fn calc_main_bounds(
+    stripes: &[Stripe],
+    groups: &[StripeProperties],
+    stretch_stripes: &[usize],
+    spacing: i32,
+    main_size: i32,
+) -> Vec<(i32, i32)> {
+    if stripes.is_empty() {
+        return Vec::new();
+    }
+    calc_stripe_bounds(main_size, stripes, groups, stretch_stripes, &[], spacing)
+}
+
+fn justify_bounds(bounds: &mut [(i32, i32)], justify: FlexJustify, main_size: i32) {
+    let count = bounds.len() as i32;
+    let used_size = bounds
+        .last()
+        .map(|&(start, size)| start + size)
+        .unwrap_or_default();
+    let free_size = std::cmp::max(0, main_size - used_size);
+    let (offset, gap) = match justify {
+        FlexJustify::Start => (0, 0),
+        FlexJustify::Center => (free_size / 2, 0),
+        FlexJustify::End => (free_size, 0),
+        FlexJustify::SpaceBetween if count > 1 => (0, free_size / (count - 1)),
+        FlexJustify::SpaceBetween => (0, 0),
+        FlexJustify::SpaceAround if count > 0 => {
+            let gap = free_size / count;
+            (gap / 2, gap)
+        }
+        FlexJustify::SpaceAround => (0, 0),
+    };
+    for (idx, (start, _)) in bounds.iter_mut().enumerate() {
+        *start += offset + gap * idx as i32;
+    }
+}
+
+fn child_height_for_width(child: &FlexChild, width: i32, min_height: i32, max_height: i32) -> i32 {
+    if !child.element.has_height_for_width() {
+        return min_height;
+    }
+    std::cmp::min(child.element.min_height_for_width(width), max_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn main_bounds(children: &[(u8, i32, i32)], spacing: i32, main_size: i32) -> Vec<(i32, i32)> {
+        let (stripes, groups): (Vec<_>, Vec<_>) = children
+            .iter()
+            .enumerate()
+            .map(|(idx, &(stretch, min_size, max_size))| {
+                main_stripe(idx, stretch, min_size, max_size)
+            })
+            .unzip();
+        let mut stretch_stripes = collect_stretch_stripes(&stripes, &groups);
+        sort_stretch_stripes(&stripes, &groups, &mut stretch_stripes);
+        calc_main_bounds(&stripes, &groups, &stretch_stripes, spacing, main_size)
+    }
+
+    fn justified(justify: FlexJustify, main_size: i32) -> Vec<(i32, i32)> {
+        let mut bounds = main_bounds(&[(0, 10, 10), (0, 20, 20), (0, 10, 10)], 5, main_size);
+        justify_bounds(&mut bounds, justify, main_size);
+        bounds
+    }
+
+    #[test]
+    fn main_bounds_distribute_stretch() {
+        assert_eq!(
+            main_bounds(&[(0, 10, 10), (1, 10, i32::MAX), (3, 10, i32::MAX)], 5, 120),
+            vec![(0, 10), (15, 25), (45, 75)]
+        );
+        assert_eq!(
+            main_bounds(&[(1, 10, 20), (1, 10, i32::MAX)], 0, 100),
+            vec![(0, 20), (20, 80)]
+        );
+        assert!(main_bounds(&[], 5, 100).is_empty());
+    }
+
+    #[test]
+    fn justify_offsets_children() {
+        assert_eq!(
+            justified(FlexJustify::Start, 100),
+            vec![(0, 10), (15, 20), (40, 10)]
+        );
+        assert_eq!(
+            justified(FlexJustify::Center, 100),
+            vec![(25, 10), (40, 20), (65, 10)]
+        );
+        assert_eq!(
+            justified(FlexJustify::End, 100),
+            vec![(50, 10), (65, 20), (90, 10)]
+        );
+        assert_eq!(
+            justified(FlexJustify::SpaceBetween, 100),
+            vec![(0, 10), (40, 20), (90, 10)]
+        );
+        assert_eq!(
+            justified(FlexJustify::SpaceAround, 100),
+            vec![(8, 10), (39, 20), (80, 10)]
+        );
+    }
+
+    #[test]
+    fn justify_without_free_space() {
+        for justify in [
+            FlexJustify::Start,
+            FlexJustify::Center,
+            FlexJustify::End,
+            FlexJustify::SpaceBetween,
+            FlexJustify::SpaceAround,
+        ] {
+            assert_eq!(justified(justify, 40), vec![(0, 10), (15, 20), (40, 10)]);
+        }
+
+        let mut single = vec![(0, 10)];
+        justify_bounds(&mut single, FlexJustify::SpaceBetween, 100);
+        assert_eq!(single, vec![(0, 10)]);
+    }
+
+    #[test]
+    fn vertical_baseline_falls_back_to_start() {
+        assert_eq!(
+            cross_align(FlexDirection::Vertical, CellAlign::Baseline),
+            CellAlign::Start
+        );
+        assert_eq!(
+            cross_align(FlexDirection::Horizontal, CellAlign::Baseline),
+            CellAlign::Baseline
+        );
+        assert_eq!(
+            cross_align(FlexDirection::Vertical, CellAlign::Center),
+            CellAlign::Center
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use std::borrow::Borrow;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use fltk::group::Group;
+use fltk::prelude::{GroupExt, WidgetBase};
+use fltk::widget::Widget;
+
+use crate::grid::{calc_widget_bounds, CellAlign};
+use crate::{IntoWidget, LayoutElement, LayoutNotifier, Padding, Size, WrapperFactory};
+
+pub struct Flow<G: GroupExt + Clone = Group> {
+    props: FlowProperties<G>,
+    cache: RefCell<FlowCache>,
+}
+
+pub struct FlowBuilder<G: GroupExt + Clone = Group, F: Borrow<WrapperFactory> = WrapperFactory> {
+    props: FlowProperties<G>,
+    factory: F,
+}
+
+struct FlowProperties<G: GroupExt + Clone> {
+    group: G,
+    padding: Padding,
+    row_spacing: i32,
+    col_spacing: i32,
+    vert_align: CellAlign,
+    children: Vec<Rc<dyn LayoutElement>>,
+    auto_refresh: bool,
+    notifier: Rc<LayoutNotifier>,
+}
+
+struct FlowCache {
+    min_sizes: Vec<Size>,
+    max_sizes: Vec<Size>,
+    min_size: Size,
+}
+
+struct FlowLine {
+    start: usize,
+    end: usize,
+    height: i32,
+}
+
+impl<G: GroupExt + Clone> LayoutElement for Flow<G> {
+    fn min_size(&self) -> Size {
+        self.cache().min_size
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        self.cache().min_sizes.len() > 1
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        let cache = self.cache();
+        let width = width - (self.props.padding.left + self.props.padding.right);
+        let lines = calc_lines(&cache.min_sizes, self.props.col_spacing, width);
+        lines_height(&lines, self.props.row_spacing)
+            + self.props.padding.top
+            + self.props.padding.bottom
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        for child in self.props.children.iter() {
+            child.invalidate();
+        }
+        self.props.notifier.take_stale();
+        *self.cache.borrow_mut() = FlowCache::new(&self.props);
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.props.notifier))
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.props.group.clone().resize(x, y, width, height);
+        self.layout_children();
+    }
+}
+
+impl Flow {
+    pub fn builder() -> FlowBuilder<Group, WrapperFactory> {
+        FlowBuilder::new(Group::default_fill())
+    }
+
+    pub fn builder_with_factory<F: Borrow<WrapperFactory>>(factory: F) -> FlowBuilder<Group, F> {
+        FlowBuilder::with_factory(Group::default_fill(), factory)
+    }
+}
+
+impl<G: GroupExt + Clone> Flow<G> {
+    pub fn group(&self) -> G {
+        self.props.group.clone()
+    }
+
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
+    pub fn layout_children(&self) {
+        if self.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+
+        let x = self.props.group.x() + self.props.padding.left;
+        let y = self.props.group.y() + self.props.padding.top;
+        let width = self.props.group.width() - (self.props.padding.left + self.props.padding.right);
+
+        let cache = self.cache();
+        let lines = calc_lines(&cache.min_sizes, self.props.col_spacing, width);
+
+        let mut line_y = y;
+        for line in lines.iter() {
+            let mut child_x = x;
+            for idx in line.start..line.end {
+                let min_size = cache.min_sizes[idx];
+                let max_size = cache.max_sizes[idx];
+                let (child_y, child_height) = calc_widget_bounds(
+                    line_y,
+                    line.height,
+                    min_size.height,
+                    max_size.height,
+                    0,
+                    0,
+                    self.props.vert_align,
+                );
+                self.props.children[idx].layout(child_x, child_y, min_size.width, child_height);
+                child_x += min_size.width + self.props.col_spacing;
+            }
+            line_y += line.height + self.props.row_spacing;
+        }
+    }
+
+    fn new(props: FlowProperties<G>) -> Self {
+        if props.auto_refresh {
+            props.notifier.enable_auto_refresh();
+        }
+        let cache = RefCell::new(FlowCache::new(&props));
+        Self { props, cache }
+    }
+
+    fn cache(&self) -> Ref<'_, FlowCache> {
+        if self.props.notifier.take_stale() {
+            *self.cache.borrow_mut() = FlowCache::new(&self.props);
+        }
+        self.cache.borrow()
+    }
+}
+
+impl FlowCache {
+    fn new<G: GroupExt + Clone>(props: &FlowProperties<G>) -> Self {
+        let min_sizes: Vec<Size> = props
+            .children
+            .iter()
+            .map(|child| child.min_size())
+            .collect();
+        let max_sizes = props
+            .children
+            .iter()
+            .zip(min_sizes.iter())
+            .map(|(child, min_size)| {
+                let max_size = child.max_size();
+                Size {
+                    width: std::cmp::max(max_size.width, min_size.width),
+                    height: std::cmp::max(max_size.height, min_size.height),
+                }
+            })
+            .collect();
+
+        let width = min_sizes
+            .iter()
+            .map(|size| size.width)
+            .max()
+            .unwrap_or_default();
+        let height = min_sizes.iter().map(|size| size.height).sum::<i32>()
+            + std::cmp::max(0, min_sizes.len() as i32 - 1) * props.row_spacing;
+
+        Self {
+            min_sizes,
+            max_sizes,
+            min_size: Size {
+                width: width + props.padding.left + props.padding.right,
+                height: height + props.padding.top + props.padding.bottom,
+            },
+        }
+    }
+}
+
+impl<G: GroupExt + Clone> FlowBuilder<G> {
+    pub fn new(group: G) -> Self {
+        Self::with_factory(group, WrapperFactory::with_defaults())
+    }
+}
+
+impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> FlowBuilder<G, F> {
+    pub fn with_factory(group: G, factory: F) -> Self {
+        Self {
+            props: FlowProperties {
+                group,
+                padding: Default::default(),
+                row_spacing: 0,
+                col_spacing: 0,
+                vert_align: CellAlign::Center,
+                children: Vec::new(),
+                auto_refresh: false,
+                notifier: LayoutNotifier::new(),
+            },
+            factory,
+        }
+    }
+
+    pub fn with_row_spacing(mut self, spacing: i32) -> Self {
+        self.props.row_spacing = std::cmp::max(0, spacing);
+        self
+    }
+
+    pub fn with_col_spacing(mut self, spacing: i32) -> Self {
+        self.props.col_spacing = std::cmp::max(0, spacing);
+        self
+    }
+
+    pub fn with_left_padding(mut self, padding: i32) -> Self {
+        self.props.padding.left = padding;
+        self
+    }
+
+    pub fn with_top_padding(mut self, padding: i32) -> Self {
+        self.props.padding.top = padding;
+        self
+    }
+
+    pub fn with_right_padding(mut self, padding: i32) -> Self {
+        self.props.padding.right = padding;
+        self
+    }
+
+    pub fn with_bottom_padding(mut self, padding: i32) -> Self {
+        self.props.padding.bottom = padding;
+        self
+    }
+
+    pub fn with_padding(mut self, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        self.props.padding = Padding {
+            left,
+            top,
+            right,
+            bottom,
+        };
+        self
+    }
+
+    pub fn with_vert_align(mut self, align: CellAlign) -> Self {
+        self.props.vert_align = align;
+        self
+    }
+
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
+    pub fn add<E: LayoutElement + 'static>(&mut self, element: E) {
+        self.add_shared(Rc::new(element));
+    }
+
+    pub fn add_shared(&mut self, element: Rc<dyn LayoutElement>) {
+        self.props.notifier.adopt(&*element);
+        self.props.children.push(element);
+    }
+
+    pub fn wrap<W: IntoWidget + 'static>(&mut self, widget: W) -> W {
+        let element = self.factory.borrow().wrap(widget.clone());
+        self.add_shared(element);
+        widget
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.add_shared(Rc::new(wrapper(widget.clone())));
+        widget
+    }
+
+    pub fn end(self) -> Flow<G> {
+        self.props.group.end();
+        Flow::new(self.props)
+    }
+}
+
+fn lines_height(lines: &[FlowLine], spacing: i32) -> i32 {
+    lines.iter().map(|line| line.height).sum::<i32>()
+        + std::cmp::max(0, lines.len() as i32 - 1) * spacing
+}
+
+fn calc_lines(min_sizes: &[Size], col_spacing: i32, width: i32) -> Vec<FlowLine> {
+    let mut lines = Vec::new();
+    let mut line = FlowLine {
+        start: 0,
+        end: 0,
+        height: 0,
+    };
+    let mut line_width = 0;
+
+    for (idx, min_size) in min_sizes.iter().enumerate() {
+        let child_width = if line.end > line.start {
+            line_width + col_spacing + min_size.width
+        } else {
+            min_size.width
+        };
+        if (line.end > line.start) && (child_width > width) {
+            lines.push(line);
+            line = FlowLine {
+                start: idx,
+                end: idx,
+                height: 0,
+            };
+            line_width = min_size.width;
+        } else {
+            line_width = child_width;
+        }
+        line.end = idx + 1;
+        line.height = std::cmp::max(line.height, min_size.height);
+    }
+    if line.end > line.start {
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizes(widths: &[i32]) -> Vec<Size> {
+        widths
+            .iter()
+            .enumerate()
+            .map(|(idx, &width)| Size {
+                width,
+                height: 10 + idx as i32,
+            })
+            .collect()
+    }
+
+    fn line_ranges(lines: &[FlowLine]) -> Vec<(usize, usize, i32)> {
+        lines
+            .iter()
+            .map(|line| (line.start, line.end, line.height))
+            .collect()
+    }
+
+    #[test]
+    fn lines_break_on_spacing() {
+        let min_sizes = sizes(&[30, 30, 30]);
+        assert_eq!(
+            line_ranges(&calc_lines(&min_sizes, 0, 60)),
+            vec![(0, 2, 11), (2, 3, 12)]
+        );
+        assert_eq!(
+            line_ranges(&calc_lines(&min_sizes, 5, 64)),
+            vec![(0, 1, 10), (1, 2, 11), (2, 3, 12)]
+        );
+        assert_eq!(
+            line_ranges(&calc_lines(&min_sizes, 5, 65)),
+            vec![(0, 2, 11), (2, 3, 12)]
+        );
+        assert_eq!(
+            line_ranges(&calc_lines(&min_sizes, 5, 100)),
+            vec![(0, 3, 12)]
+        );
+    }
+
+    #[test]
+    fn oversized_children_get_own_line() {
+        let min_sizes = sizes(&[10, 80, 10]);
+        assert_eq!(
+            line_ranges(&calc_lines(&min_sizes, 0, 50)),
+            vec![(0, 1, 10), (1, 2, 11), (2, 3, 12)]
+        );
+        assert!(calc_lines(&[], 0, 50).is_empty());
+    }
+
+    #[test]
+    fn height_decreases_with_width() {
+        let min_sizes = vec![
+            Size {
+                width: 20,
+                height: 10,
+            };
+            4
+        ];
+        let heights: Vec<i32> = [20, 45, 70, 95]
+            .iter()
+            .map(|&width| lines_height(&calc_lines(&min_sizes, 5, width), 2))
+            .collect();
+        assert_eq!(heights, vec![46, 22, 22, 10]);
+        assert!(heights.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+}
@@ -0,0 +1,269 @@
+use std::borrow::Borrow;
+use std::rc::Rc;
+
+use fltk::enums::{Align, Font};
+use fltk::frame::Frame;
+use fltk::group::Group;
+use fltk::prelude::{GroupExt, WidgetBase, WidgetExt};
+use fltk::widget::Widget;
+
+use crate::frame::FrameElement;
+use crate::grid::{CellAlign, CellBuilder, Grid, GridBuilder};
+use crate::{IntoWidget, LayoutElement, LayoutNotifier, LayoutWidgetWrapper, Size, WrapperFactory};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormLabelAlign {
+    Start,
+    End,
+}
+
+pub struct Form<G: GroupExt + Clone = Group> {
+    grid: Grid<G>,
+}
+
+pub struct FormBuilder<G: GroupExt + Clone = Group, F: Borrow<WrapperFactory> = WrapperFactory> {
+    grid: GridBuilder<G, F>,
+    label_align: FormLabelAlign,
+}
+
+impl FormLabelAlign {
+    pub fn platform_default() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Start
+        } else {
+            Self::End
+        }
+    }
+}
+
+impl<G: GroupExt + Clone> LayoutElement for Form<G> {
+    fn min_size(&self) -> Size {
+        self.grid.min_size()
+    }
+
+    fn max_size(&self) -> Size {
+        self.grid.max_size()
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        self.grid.has_height_for_width()
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        self.grid.min_height_for_width(width)
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        self.grid.widget()
+    }
+
+    fn invalidate(&self) {
+        self.grid.invalidate();
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        self.grid.notifier()
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.grid.layout(x, y, width, height);
+    }
+}
+
+impl Form {
+    pub fn builder() -> FormBuilder<Group, WrapperFactory> {
+        FormBuilder::new(Group::default_fill())
+    }
+
+    pub fn builder_with_factory<F: Borrow<WrapperFactory>>(factory: F) -> FormBuilder<Group, F> {
+        FormBuilder::with_factory(Group::default_fill(), factory)
+    }
+}
+
+impl<G: GroupExt + Clone> Form<G> {
+    pub fn group(&self) -> G {
+        self.grid.group()
+    }
+
+    pub fn grid(&self) -> &Grid<G> {
+        &self.grid
+    }
+
+    pub fn grid_mut(&mut self) -> &mut Grid<G> {
+        &mut self.grid
+    }
+
+    pub fn refresh(&self) {
+        self.grid.refresh();
+    }
+
+    pub fn layout_children(&self) {
+        self.grid.layout_children();
+    }
+}
+
+impl<G: GroupExt + Clone> FormBuilder<G> {
+    pub fn new(group: G) -> Self {
+        Self::with_factory(group, WrapperFactory::with_defaults())
+    }
+}
+
+impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> FormBuilder<G, F> {
+    pub fn with_factory(group: G, factory: F) -> Self {
+        let mut grid = GridBuilder::with_factory(group, factory);
+        grid.col().add();
+        grid.col().with_stretch(1).add();
+        Self {
+            grid,
+            label_align: FormLabelAlign::platform_default(),
+        }
+    }
+
+    pub fn with_row_spacing(mut self, spacing: i32) -> Self {
+        self.grid = self.grid.with_row_spacing(spacing);
+        self
+    }
+
+    pub fn with_col_spacing(mut self, spacing: i32) -> Self {
+        self.grid = self.grid.with_col_spacing(spacing);
+        self
+    }
+
+    pub fn with_left_padding(mut self, padding: i32) -> Self {
+        self.grid = self.grid.with_left_padding(padding);
+        self
+    }
+
+    pub fn with_top_padding(mut self, padding: i32) -> Self {
+        self.grid = self.grid.with_top_padding(padding);
+        self
+    }
+
+    pub fn with_right_padding(mut self, padding: i32) -> Self {
+        self.grid = self.grid.with_right_padding(padding);
+        self
+    }
+
+    pub fn with_bottom_padding(mut self, padding: i32) -> Self {
+        self.grid = self.grid.with_bottom_padding(padding);
+        self
+    }
+
+    pub fn with_padding(mut self, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        self.grid = self.grid.with_padding(left, top, right, bottom);
+        self
+    }
+
+    pub fn with_label_align(mut self, align: FormLabelAlign) -> Self {
+        self.label_align = align;
+        self
+    }
+
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.grid = self.grid.with_auto_refresh(auto_refresh);
+        self
+    }
+
+    pub fn grid(&mut self) -> &mut GridBuilder<G, F> {
+        &mut self.grid
+    }
+
+    pub fn add_field<E: LayoutElement + 'static>(&mut self, label: &str, element: E) {
+        self.add_shared_field(label, Rc::new(element));
+    }
+
+    pub fn add_shared_field(&mut self, label: &str, element: Rc<dyn LayoutElement>) {
+        let row = self.add_label(label);
+        self.field_cell(row).add_shared(element);
+    }
+
+    pub fn wrap_field<W: IntoWidget + 'static>(&mut self, label: &str, widget: W) -> W {
+        let row = self.add_label(label);
+        self.field_cell(row).wrap(widget)
+    }
+
+    pub fn wrap_field_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        label: &str,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        let row = self.add_label(label);
+        self.field_cell(row).wrap_with(widget, wrapper)
+    }
+
+    pub fn add_full<E: LayoutElement + 'static>(&mut self, element: E) {
+        self.add_shared_full(Rc::new(element));
+    }
+
+    pub fn add_shared_full(&mut self, element: Rc<dyn LayoutElement>) {
+        self.full_cell().add_shared(element);
+    }
+
+    pub fn wrap_full<W: IntoWidget + 'static>(&mut self, widget: W) -> W {
+        self.full_cell().wrap(widget)
+    }
+
+    pub fn wrap_full_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.full_cell().wrap_with(widget, wrapper)
+    }
+
+    pub fn heading(&mut self, text: &str) -> Frame {
+        let mut heading = Frame::default().with_label(text);
+        heading.set_align(Align::Inside | Align::Left);
+        heading.set_label_font(Font::HelveticaBold);
+        self.full_cell()
+            .with_horz_align(CellAlign::Stretch)
+            .wrap_with(heading, FrameElement::wrap)
+    }
+
+    pub fn help(&mut self, text: &str) -> Frame {
+        let mut help = Frame::default().with_label(text);
+        help.set_align(Align::Inside | Align::Left | Align::Wrap);
+        help.set_label_size(help.label_size() - 2);
+        let row = self.grid.append_row();
+        self.grid
+            .cell_builder(row, 1, 1, 1)
+            .with_horz_align(CellAlign::Stretch)
+            .wrap_with(help, FrameElement::wrap)
+    }
+
+    pub fn end(self) -> Form<G> {
+        Form {
+            grid: self.grid.end(),
+        }
+    }
+
+    fn add_label(&mut self, text: &str) -> usize {
+        let (horz_align, align) = match self.label_align {
+            FormLabelAlign::Start => (CellAlign::Start, Align::Left),
+            FormLabelAlign::End => (CellAlign::End, Align::Right),
+        };
+        let mut label = Frame::default().with_label(text);
+        label.set_align(Align::Inside | align);
+
+        let row = self.grid.append_row();
+        self.grid
+            .cell_builder(row, 0, 1, 1)
+            .with_horz_align(horz_align)
+            .with_vert_align(CellAlign::Baseline)
+            .wrap_with(label, FrameElement::wrap);
+        row
+    }
+
+    fn field_cell(&mut self, row: usize) -> CellBuilder<'_, G, F> {
+        self.grid
+            .cell_builder(row, 1, 1, 1)
+            .with_horz_align(CellAlign::Stretch)
+            .with_vert_align(CellAlign::Baseline)
+    }
+
+    fn full_cell(&mut self) -> CellBuilder<'_, G, F> {
+        let row = self.grid.append_row();
+        self.grid.cell_builder(row, 0, 1, 2)
+    }
+}
@@ -1,7 +1,9 @@
 use std::ops::{Deref, DerefMut};
 
+use fltk::enums::Align;
 use fltk::frame::Frame;
 use fltk::prelude::*;
+use fltk::widget::Widget;
 
 use super::{LayoutElement, LayoutWidgetWrapper, Size};
 
@@ -17,6 +19,23 @@ impl LayoutWidgetWrapper<Frame> for FrameElement {
 
 impl LayoutElement for FrameElement {
     fn min_size(&self) -> Size {
+        if self.has_height_for_width() {
+            let frame_width = self.frame_width();
+            fltk::draw::set_font(self.widget.label_font(), self.widget.label_size());
+            let label_width = self
+                .widget
+                .label()
+                .split_whitespace()
+                .map(|word| fltk::draw::measure(word, true).0)
+                .max()
+                .unwrap_or_default();
+            let width = label_width + 2 * frame_width;
+            return Size {
+                width,
+                height: self.min_height_for_width(width),
+            };
+        }
+
         let (label_width, label_height) = self.widget.measure_label();
         let frame = self.widget.frame();
         let frame_dx = frame.dx();
@@ -31,11 +50,59 @@ impl LayoutElement for FrameElement {
         }
     }
 
+    fn has_height_for_width(&self) -> bool {
+        self.widget.align().contains(Align::Wrap)
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        if !self.has_height_for_width() {
+            return self.min_size().height;
+        }
+
+        let frame = self.widget.frame();
+        let frame_height = frame.dy() + frame.dh();
+        let label_width = std::cmp::max(1, width - 2 * self.frame_width());
+        fltk::draw::set_font(self.widget.label_font(), self.widget.label_size());
+        let (_, label_height) = fltk::draw::wrap_measure(&self.widget.label(), label_width, true);
+        label_height + frame_height
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        self.baseline_for_height(self.min_size().height)
+    }
+
+    fn baseline_for_height(&self, height: i32) -> Option<i32> {
+        let (_, label_height) = self.widget.measure_label();
+        let frame = self.widget.frame();
+        let content_height = height - (frame.dy() + frame.dh());
+        let align = self.widget.align();
+        let label_start = if align.contains(Align::Top) {
+            0
+        } else if align.contains(Align::Bottom) {
+            std::cmp::max(0, content_height - label_height)
+        } else {
+            std::cmp::max(0, content_height - label_height) / 2
+        };
+        fltk::draw::set_font(self.widget.label_font(), self.widget.label_size());
+        Some(frame.dy() + label_start + fltk::draw::height() - fltk::draw::descent())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.widget.clone().resize(x, y, width, height);
     }
 }
 
+impl FrameElement {
+    fn frame_width(&self) -> i32 {
+        let frame = self.widget.frame();
+        frame.dx() + frame.dw()
+    }
+}
+
 impl Deref for FrameElement {
     type Target = Frame;
     fn deref(&self) -> &Self::Target {
@@ -1,30 +1,38 @@
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
+use std::cell::{Ref, RefCell};
 use std::rc::Rc;
 
 use fltk::group::Group;
 use fltk::prelude::*;
+use fltk::widget::Widget;
 
-use crate::WrapperFactory;
+use crate::{LayoutNotifier, WrapperFactory};
 
 use super::{LayoutElement, Padding, Size};
 
 mod builder;
+mod error;
+#[cfg(any(debug_assertions, test))]
+mod validate;
 
 pub use builder::{CellBuilder, GridBuilder, StripeBuilder};
+pub use error::{GridError, GridTemplateError};
 
-#[derive(Debug, Clone, Copy)]
+use builder::StripeKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellAlign {
     Start,
     Center,
     End,
     Stretch,
+    Baseline,
 }
 
 pub struct Grid<G: GroupExt + Clone = Group> {
     props: GridProperties<G>,
-    stretch_rows: Vec<usize>,
-    stretch_cols: Vec<usize>,
-    min_size: Size,
+    cache: RefCell<Option<GridCache>>,
+    factory: Option<Rc<WrapperFactory>>,
 }
 
 struct GridProperties<G: GroupExt + Clone = Group> {
@@ -37,14 +45,33 @@ struct GridProperties<G: GroupExt + Clone = Group> {
     groups: Vec<StripeProperties>,
     rows: Vec<Stripe>,
     cols: Vec<Stripe>,
+    auto_refresh: bool,
+    default_cell_padding: Padding,
+    default_row_align: Vec<CellAlign>,
+    default_col_align: Vec<CellAlign>,
+    notifier: Rc<LayoutNotifier>,
+}
+
+struct GridCache {
+    cells: Vec<CellSizes>,
+    spans: Vec<CellSizes>,
+    groups: Vec<StripeProperties>,
+    stretch_rows: Vec<usize>,
+    stretch_cols: Vec<usize>,
+    collapsed_rows: Vec<bool>,
+    collapsed_cols: Vec<bool>,
+    min_size: Size,
+    max_size: Size,
+    height_for_width: bool,
 }
 
 struct Cell {
     element: Rc<dyn LayoutElement>,
-    min_size: Size,
+    widget: Option<Widget>,
     props: CellProperties,
 }
 
+#[derive(Clone, Copy)]
 struct CellProperties {
     row: usize,
     col: usize,
@@ -53,21 +80,35 @@ struct CellProperties {
     padding: Padding,
     horz_align: CellAlign,
     vert_align: CellAlign,
+    max_size: Size,
 }
 
 #[derive(Debug, Clone, Copy)]
-struct StripeProperties {
-    stretch: u8,
-    min_size: i32,
+struct CellSizes {
+    min_size: Size,
+    max_size: Size,
+    height_for_width: bool,
+    baseline: Option<i32>,
+    visible: bool,
 }
 
-struct Stripe {
-    cells: Vec<StripeCell>,
-    group_idx: usize,
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StripeProperties {
+    pub(crate) stretch: f64,
+    pub(crate) percentage: Option<f64>,
+    pub(crate) min_size: i32,
+    pub(crate) max_size: i32,
+    pub(crate) shrink_priority: u8,
+    pub(crate) collapsible: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum StripeCell {
+pub(crate) struct Stripe {
+    pub(crate) cells: Vec<StripeCell>,
+    pub(crate) group_idx: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StripeCell {
     Free,
     Skipped,
     Cell(usize),
@@ -84,9 +125,91 @@ impl StripeCell {
     }
 }
 
+const MIN_STRETCH: f64 = 1.0e-9;
+const MAX_STRETCH: f64 = 1.0e9;
+
+impl StripeProperties {
+    pub(crate) fn set_stretch(&mut self, stretch: f64) {
+        self.stretch = if stretch.is_nan() || (stretch < MIN_STRETCH) {
+            0.0
+        } else {
+            stretch.min(MAX_STRETCH)
+        };
+    }
+
+    pub(crate) fn set_percentage(&mut self, percentage: f64) {
+        self.percentage =
+            Some(if percentage.is_nan() { 0.0 } else { percentage.clamp(0.0, 100.0) });
+    }
+
+    pub(crate) fn is_stretch(&self) -> bool {
+        self.percentage.is_none() && (self.stretch > 0.0)
+    }
+
+    fn fixed_size(&self, content_size: i32) -> i32 {
+        match self.percentage {
+            Some(percentage) => {
+                let size = (content_size as f64 * percentage / 100.0).round() as i32;
+                std::cmp::max(self.min_size, std::cmp::min(size, self.max_size))
+            }
+            None => self.min_size,
+        }
+    }
+}
+
 impl<G: GroupExt + Clone> LayoutElement for Grid<G> {
     fn min_size(&self) -> Size {
-        self.min_size
+        self.cache().min_size
+    }
+
+    fn max_size(&self) -> Size {
+        self.cache().max_size
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        self.cache().height_for_width
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        let cache = self.cache();
+        if !cache.height_for_width {
+            return cache.min_size.height;
+        }
+
+        let width = width - (self.props.padding.left + self.props.padding.right);
+        let col_bounds = calc_stripe_bounds(
+            width,
+            &self.props.cols,
+            &cache.groups,
+            &cache.stretch_cols,
+            &cache.collapsed_cols,
+            self.props.col_spacing,
+        );
+        let row_groups = cache.row_groups_for_width(&self.props, &col_bounds);
+
+        span_size(
+            &self.props.rows,
+            &row_groups,
+            &cache.collapsed_rows,
+            self.props.row_spacing,
+        ) + self.props.padding.top
+            + self.props.padding.bottom
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        for cell in self.props.cells.iter().chain(self.props.spans.iter()) {
+            cell.element.invalidate();
+        }
+        self.props.notifier.take_stale();
+        *self.cache.borrow_mut() = Some(GridCache::new(&self.props));
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.props.notifier))
     }
 
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
@@ -110,183 +233,588 @@ impl<G: GroupExt + Clone> Grid<G> {
         self.props.group.clone()
     }
 
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
     pub fn layout_children(&self) {
+        if self.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+
         let x = self.props.group.x() + self.props.padding.left;
         let y = self.props.group.y() + self.props.padding.top;
         let width = self.props.group.width() - (self.props.padding.left + self.props.padding.right);
         let height =
             self.props.group.height() - (self.props.padding.top + self.props.padding.bottom);
 
+        if self.cache().visibility_changed(&self.props) {
+            *self.cache.borrow_mut() = Some(GridCache::new(&self.props));
+            self.props.notifier.mark_ancestors_stale();
+        }
+        let cache = self.cache();
+
         // TODO: Eliminate unnecessary allocation
         let col_bounds = calc_stripe_bounds(
             width,
             &self.props.cols,
-            &self.props.groups,
-            &self.stretch_cols,
+            &cache.groups,
+            &cache.stretch_cols,
+            &cache.collapsed_cols,
             self.props.col_spacing,
         );
+        let row_groups = cache.row_groups_for_width(&self.props, &col_bounds);
         let row_bounds = calc_stripe_bounds(
             height,
             &self.props.rows,
-            &self.props.groups,
-            &self.stretch_rows,
+            &row_groups,
+            &cache.stretch_rows,
+            &cache.collapsed_rows,
             self.props.row_spacing,
         );
 
-        for cell in self.props.cells.iter() {
-            let (cell_x, cell_width) = col_bounds[cell.props.col];
-            let (cell_y, cell_height) = row_bounds[cell.props.row];
+        let row_baselines: Vec<_> = (0..self.props.rows.len())
+            .map(|row| cache.row_baseline(&self.props, row, Some(&col_bounds)))
+            .collect();
+
+        let cells = self.props.cells.iter().zip(cache.cells.iter());
+        let spans = self.props.spans.iter().zip(cache.spans.iter());
+        for (cell, sizes) in cells.chain(spans) {
+            if cache.is_collapsed(&cell.props) {
+                continue;
+            }
+            let (cell_x, cell_width) =
+                span_bounds(&col_bounds, cell.props.col, cell.props.col_span);
+            let (cell_y, cell_height) =
+                span_bounds(&row_bounds, cell.props.row, cell.props.row_span);
+
             let (widget_x, widget_width) = calc_widget_bounds(
-                x,
-                cell_x,
+                x + cell_x,
                 cell_width,
-                cell.min_size.width,
+                sizes.min_size.width,
+                sizes.max_size.width,
                 cell.props.padding.left,
                 cell.props.padding.right,
                 cell.props.horz_align,
             );
-            let (widget_y, widget_height) = calc_widget_bounds(
-                y,
-                cell_y,
-                cell_height,
-                cell.min_size.height,
-                cell.props.padding.top,
-                cell.props.padding.bottom,
-                cell.props.vert_align,
-            );
+            let baseline = match (cell.props.vert_align, row_baselines[cell.props.row]) {
+                (CellAlign::Baseline, Some(block)) => {
+                    let height =
+                        std::cmp::min(cell_height, cell.min_height_for_width(sizes, cell_width));
+                    cell.baseline_for_height(sizes, height)
+                        .map(|baseline| (block, baseline, height))
+                }
+                _ => None,
+            };
+            let (widget_y, widget_height) = match baseline {
+                Some((block, baseline, height)) => calc_baseline_bounds(
+                    y + cell_y,
+                    row_bounds[cell.props.row].1,
+                    block,
+                    baseline,
+                    height,
+                    cell.props.padding.top,
+                    cell.props.padding.bottom,
+                ),
+                None => calc_widget_bounds(
+                    y + cell_y,
+                    cell_height,
+                    sizes.min_size.height,
+                    sizes.max_size.height,
+                    cell.props.padding.top,
+                    cell.props.padding.bottom,
+                    cell.props.vert_align,
+                ),
+            };
             cell.element
                 .layout(widget_x, widget_y, widget_width, widget_height);
         }
+    }
 
-        for span in self.props.spans.iter() {
-            let left_col = span.props.col;
-            let right_col = left_col + span.props.col_span - 1;
-            let span_x = col_bounds[left_col].0;
-            let span_width = col_bounds[right_col].0 + col_bounds[right_col].1 - span_x;
+    pub fn num_rows(&self) -> usize {
+        self.props.rows.len()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.props.cols.len()
+    }
 
-            let top_row = span.props.row;
-            let bottom_row = top_row + span.props.row_span - 1;
-            let span_y = row_bounds[top_row].0;
-            let span_height = row_bounds[bottom_row].0 + row_bounds[bottom_row].1 - span_y;
+    pub fn insert_row(&mut self, row: usize) -> StripeBuilder<'_, G> {
+        *self.cache.get_mut() = None;
+        StripeBuilder::new(&mut self.props, StripeKind::Row, None, Some(row))
+    }
 
-            let (widget_x, widget_width) = calc_widget_bounds(
-                x,
-                span_x,
-                span_width,
-                span.min_size.width,
-                span.props.padding.left,
-                span.props.padding.right,
-                span.props.horz_align,
-            );
-            let (widget_y, widget_height) = calc_widget_bounds(
-                y,
-                span_y,
-                span_height,
-                span.min_size.height,
-                span.props.padding.top,
-                span.props.padding.bottom,
-                span.props.vert_align,
-            );
-            span.element
-                .layout(widget_x, widget_y, widget_width, widget_height);
+    pub fn insert_col(&mut self, col: usize) -> StripeBuilder<'_, G> {
+        *self.cache.get_mut() = None;
+        StripeBuilder::new(&mut self.props, StripeKind::Column, None, Some(col))
+    }
+
+    pub fn remove_row(&mut self, row: usize) {
+        *self.cache.get_mut() = None;
+        self.props.remove_stripe(StripeKind::Row, row);
+    }
+
+    pub fn remove_col(&mut self, col: usize) {
+        *self.cache.get_mut() = None;
+        self.props.remove_stripe(StripeKind::Column, col);
+    }
+
+    pub fn set_factory(&mut self, factory: Rc<WrapperFactory>) {
+        self.factory = Some(factory);
+    }
+
+    pub fn set_cell(
+        &mut self,
+        row: usize,
+        col: usize,
+    ) -> Result<CellBuilder<'_, G, Rc<WrapperFactory>>, GridError> {
+        self.set_span(row, col, 1, 1)
+    }
+
+    pub fn set_span(
+        &mut self,
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+    ) -> Result<CellBuilder<'_, G, Rc<WrapperFactory>>, GridError> {
+        self.prepare_area(row, col, row_span, col_span)?;
+        let factory = self
+            .factory
+            .get_or_insert_with(|| Rc::new(WrapperFactory::with_defaults()));
+        Ok(CellBuilder::new(
+            &mut self.props,
+            factory,
+            row,
+            col,
+            row_span,
+            col_span,
+        ))
+    }
+
+    pub fn set_cell_with_factory<'l, F: Borrow<WrapperFactory>>(
+        &'l mut self,
+        row: usize,
+        col: usize,
+        factory: &'l F,
+    ) -> Result<CellBuilder<'l, G, F>, GridError> {
+        self.set_span_with_factory(row, col, 1, 1, factory)
+    }
+
+    pub fn set_span_with_factory<'l, F: Borrow<WrapperFactory>>(
+        &'l mut self,
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+        factory: &'l F,
+    ) -> Result<CellBuilder<'l, G, F>, GridError> {
+        self.prepare_area(row, col, row_span, col_span)?;
+        Ok(CellBuilder::new(
+            &mut self.props,
+            factory,
+            row,
+            col,
+            row_span,
+            col_span,
+        ))
+    }
+
+    pub fn clear_cell(&mut self, row: usize, col: usize) -> bool {
+        *self.cache.get_mut() = None;
+        self.props.clear_cell(row, col)
+    }
+
+    #[cfg(debug_assertions)]
+    pub fn check_invariants(&self) {
+        if let Err(err) = self.props.validate() {
+            panic!("grid invariant violated: {}", err);
         }
     }
 
     fn new(props: GridProperties<G>) -> Self {
-        let stretch_rows = collect_stretch_stripes(&props.rows, &props.groups);
-        let stretch_cols = collect_stretch_stripes(&props.cols, &props.groups);
-
-        let mut grid = Self {
+        if props.auto_refresh {
+            props.notifier.enable_auto_refresh();
+        }
+        let cache = RefCell::new(Some(GridCache::new(&props)));
+        Self {
             props,
-            stretch_rows,
-            stretch_cols,
+            cache,
+            factory: None,
+        }
+    }
+
+    fn prepare_area(
+        &mut self,
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+    ) -> Result<(), GridError> {
+        if (row_span == 0) || (col_span == 0) {
+            return Err(GridError::ZeroSpan { row_span, col_span });
+        }
+        self.props
+            .check_span_available(row, col, row_span, col_span, true)?;
+
+        *self.cache.get_mut() = None;
+        for cell_row in row..(row + row_span) {
+            for cell_col in col..(col + col_span) {
+                self.props.clear_cell(cell_row, cell_col);
+            }
+        }
+        Ok(())
+    }
+
+    fn cache(&self) -> Ref<'_, GridCache> {
+        if self.props.notifier.take_stale() || self.cache.borrow().is_none() {
+            *self.cache.borrow_mut() = Some(GridCache::new(&self.props));
+        }
+        Ref::map(self.cache.borrow(), |cache| cache.as_ref().unwrap())
+    }
+}
+
+impl GridCache {
+    fn new<G: GroupExt + Clone>(props: &GridProperties<G>) -> Self {
+        let mut cache = Self {
+            cells: props.cells.iter().map(Cell::calc_sizes).collect(),
+            spans: props.spans.iter().map(Cell::calc_sizes).collect(),
+            groups: props.groups.clone(),
+            stretch_rows: collect_stretch_stripes(&props.rows, &props.groups),
+            stretch_cols: collect_stretch_stripes(&props.cols, &props.groups),
+            collapsed_rows: Vec::new(),
+            collapsed_cols: Vec::new(),
             min_size: Default::default(),
+            max_size: Default::default(),
+            height_for_width: false,
         };
 
-        grid.cache_min_sizes();
+        cache.cache_collapsed_stripes(props);
+        cache.cache_cell_min_sizes(props);
+        cache.cache_span_min_sizes(props);
+
+        cache.min_size.width = span_size(
+            &props.cols,
+            &cache.groups,
+            &cache.collapsed_cols,
+            props.col_spacing,
+        ) + props.padding.left
+            + props.padding.right;
+        cache.min_size.height = span_size(
+            &props.rows,
+            &cache.groups,
+            &cache.collapsed_rows,
+            props.row_spacing,
+        ) + props.padding.top
+            + props.padding.bottom;
+
+        cache.cache_max_sizes(props);
 
-        sort_stretch_stripes(&grid.props.rows, &grid.props.groups, &mut grid.stretch_rows);
-        sort_stretch_stripes(&grid.props.cols, &grid.props.groups, &mut grid.stretch_cols);
+        cache.height_for_width = cache
+            .cells
+            .iter()
+            .chain(cache.spans.iter())
+            .any(|sizes| sizes.height_for_width);
 
-        grid
+        sort_stretch_stripes(&props.rows, &cache.groups, &mut cache.stretch_rows);
+        sort_stretch_stripes(&props.cols, &cache.groups, &mut cache.stretch_cols);
+
+        cache
     }
 
-    fn cache_min_sizes(&mut self) {
-        self.cache_cell_min_sizes();
-        self.cache_span_min_sizes();
+    fn visibility_changed<G: GroupExt + Clone>(&self, props: &GridProperties<G>) -> bool {
+        if !self.groups.iter().any(|group| group.collapsible) {
+            return false;
+        }
+        props
+            .cells
+            .iter()
+            .zip(self.cells.iter())
+            .chain(props.spans.iter().zip(self.spans.iter()))
+            .any(|(cell, sizes)| cell.element.is_visible() != sizes.visible)
+    }
 
-        self.min_size.width =
-            span_size(&self.props.cols, &self.props.groups, self.props.col_spacing)
-                + self.props.padding.left
-                + self.props.padding.right;
-        self.min_size.height =
-            span_size(&self.props.rows, &self.props.groups, self.props.row_spacing)
-                + self.props.padding.top
-                + self.props.padding.bottom;
+    fn is_collapsed(&self, props: &CellProperties) -> bool {
+        let rows = props.row..(props.row + props.row_span);
+        let cols = props.col..(props.col + props.col_span);
+        self.collapsed_rows[rows].contains(&true) || self.collapsed_cols[cols].contains(&true)
     }
 
-    fn cache_cell_min_sizes(&mut self) {
-        for cell in self.props.cells.iter_mut() {
-            cell.cache_min_size();
+    fn cache_collapsed_stripes<G: GroupExt + Clone>(&mut self, props: &GridProperties<G>) {
+        self.collapsed_rows = collect_collapsed_stripes(&props.rows, &props.groups, &self.cells);
+        self.collapsed_cols = collect_collapsed_stripes(&props.cols, &props.groups, &self.cells);
+        for (span, sizes) in props.spans.iter().zip(self.spans.iter()) {
+            if sizes.visible {
+                let rows = span.props.row..(span.props.row + span.props.row_span);
+                let cols = span.props.col..(span.props.col + span.props.col_span);
+                self.collapsed_rows[rows].fill(false);
+                self.collapsed_cols[cols].fill(false);
+            }
+        }
+
+        for (cell, sizes) in props.cells.iter().zip(self.cells.iter_mut()) {
+            if self.collapsed_rows[cell.props.row] || self.collapsed_cols[cell.props.col] {
+                sizes.collapse();
+            }
         }
-        for col in self.props.cols.iter_mut() {
-            self.props.groups[col.group_idx].min_size = col
+        for (span, sizes) in props.spans.iter().zip(self.spans.iter_mut()) {
+            let rows = span.props.row..(span.props.row + span.props.row_span);
+            let cols = span.props.col..(span.props.col + span.props.col_span);
+            if self.collapsed_rows[rows].contains(&true)
+                || self.collapsed_cols[cols].contains(&true)
+            {
+                sizes.collapse();
+            }
+        }
+    }
+
+    fn cache_cell_min_sizes<G: GroupExt + Clone>(&mut self, props: &GridProperties<G>) {
+        for col in props.cols.iter() {
+            self.groups[col.group_idx].min_size = col
                 .cells
                 .iter()
                 .filter_map(StripeCell::cell_idx)
-                .map(|idx| self.props.cells[idx].min_size.width)
-                .fold(self.props.groups[col.group_idx].min_size, std::cmp::max);
+                .map(|idx| self.cells[idx].min_size.width)
+                .fold(self.groups[col.group_idx].min_size, std::cmp::max);
         }
-        for row in self.props.rows.iter_mut() {
-            self.props.groups[row.group_idx].min_size = row
+        for (row_idx, row) in props.rows.iter().enumerate() {
+            let baseline_height = self
+                .row_baseline(props, row_idx, None)
+                .map(|(ascent, descent)| ascent + descent)
+                .unwrap_or_default();
+            self.groups[row.group_idx].min_size = row
                 .cells
                 .iter()
                 .filter_map(StripeCell::cell_idx)
-                .map(|idx| self.props.cells[idx].min_size.height)
-                .fold(self.props.groups[row.group_idx].min_size, std::cmp::max);
+                .map(|idx| self.cells[idx].min_size.height)
+                .fold(
+                    std::cmp::max(self.groups[row.group_idx].min_size, baseline_height),
+                    std::cmp::max,
+                );
         }
     }
 
-    fn cache_span_min_sizes(&mut self) {
-        for span in self.props.spans.iter_mut() {
-            span.cache_min_size();
+    fn row_baseline<G: GroupExt + Clone>(
+        &self,
+        props: &GridProperties<G>,
+        row: usize,
+        col_bounds: Option<&[(i32, i32)]>,
+    ) -> Option<(i32, i32)> {
+        let cells = props.rows[row]
+            .cells
+            .iter()
+            .filter_map(StripeCell::cell_idx)
+            .map(|idx| (&props.cells[idx], &self.cells[idx]));
+        let spans = props
+            .spans
+            .iter()
+            .zip(self.spans.iter())
+            .filter(|(span, _)| span.props.row == row);
+        let blocks = cells.chain(spans).filter_map(|(cell, sizes)| {
+            if !matches!(cell.props.vert_align, CellAlign::Baseline) {
+                return None;
+            }
+            let height = match col_bounds {
+                Some(col_bounds) => cell.min_height_for_width(
+                    sizes,
+                    span_bounds(col_bounds, cell.props.col, cell.props.col_span).1,
+                ),
+                None => sizes.min_size.height,
+            };
+            let baseline = cell.baseline_for_height(sizes, height)?;
+            if cell.props.row_span > 1 {
+                return Some((baseline, 0));
+            }
+            Some((baseline, height))
+        });
+        baseline_block(blocks)
+    }
 
+    fn cache_span_min_sizes<G: GroupExt + Clone>(&mut self, props: &GridProperties<G>) {
+        for (span, sizes) in props.spans.iter().zip(self.spans.iter()) {
             let top = span.props.row;
             let bottom = top + span.props.row_span;
             let left = span.props.col;
             let right = left + span.props.col_span;
 
             adjust_span_stripes(
-                span.min_size.width,
-                &self.props.cols[left..right],
-                &mut self.props.groups,
-                self.props.col_spacing,
+                sizes.min_size.width,
+                &props.cols[left..right],
+                &mut self.groups,
+                props.col_spacing,
+            );
+            adjust_span_stripes(
+                sizes.min_size.height,
+                &props.rows[top..bottom],
+                &mut self.groups,
+                props.row_spacing,
             );
+        }
+    }
+
+    fn cache_max_sizes<G: GroupExt + Clone>(&mut self, props: &GridProperties<G>) {
+        cache_stripe_max_sizes(
+            &props.cols,
+            &props.groups,
+            &mut self.groups,
+            &self.cells,
+            |sizes| sizes.max_size.width,
+        );
+        cache_stripe_max_sizes(
+            &props.rows,
+            &props.groups,
+            &mut self.groups,
+            &self.cells,
+            |sizes| sizes.max_size.height,
+        );
+
+        self.max_size.width = span_max_size(
+            &props.cols,
+            &self.groups,
+            &self.collapsed_cols,
+            props.col_spacing,
+        )
+        .saturating_add(props.padding.left + props.padding.right);
+        self.max_size.height = span_max_size(
+            &props.rows,
+            &self.groups,
+            &self.collapsed_rows,
+            props.row_spacing,
+        )
+        .saturating_add(props.padding.top + props.padding.bottom);
+    }
+
+    fn row_groups_for_width<G: GroupExt + Clone>(
+        &self,
+        props: &GridProperties<G>,
+        col_bounds: &[(i32, i32)],
+    ) -> Cow<'_, [StripeProperties]> {
+        if !self.height_for_width {
+            return Cow::Borrowed(&self.groups);
+        }
+
+        let mut groups = self.groups.clone();
+        for row in props.rows.iter() {
+            groups[row.group_idx] = props.groups[row.group_idx];
+        }
+        for (row_idx, row) in props.rows.iter().enumerate() {
+            let baseline_height = self
+                .row_baseline(props, row_idx, Some(col_bounds))
+                .map(|(ascent, descent)| ascent + descent)
+                .unwrap_or_default();
+            groups[row.group_idx].min_size = row
+                .cells
+                .iter()
+                .filter_map(StripeCell::cell_idx)
+                .map(|idx| {
+                    let cell = &props.cells[idx];
+                    cell.min_height_for_width(&self.cells[idx], col_bounds[cell.props.col].1)
+                })
+                .fold(
+                    std::cmp::max(groups[row.group_idx].min_size, baseline_height),
+                    std::cmp::max,
+                );
+        }
+        for (span, sizes) in props.spans.iter().zip(self.spans.iter()) {
+            let top = span.props.row;
+            let bottom = top + span.props.row_span;
+            let left_col = span.props.col;
+            let right_col = left_col + span.props.col_span - 1;
+            let span_width =
+                col_bounds[right_col].0 + col_bounds[right_col].1 - col_bounds[left_col].0;
+
             adjust_span_stripes(
-                span.min_size.height,
-                &self.props.rows[top..bottom],
-                &mut self.props.groups,
-                self.props.row_spacing,
+                span.min_height_for_width(sizes, span_width),
+                &props.rows[top..bottom],
+                &mut groups,
+                props.row_spacing,
             );
         }
+        cache_stripe_max_sizes(
+            &props.rows,
+            &props.groups,
+            &mut groups,
+            &self.cells,
+            |sizes| sizes.max_size.height,
+        );
+
+        Cow::Owned(groups)
+    }
+}
+
+impl CellSizes {
+    fn collapse(&mut self) {
+        self.min_size = Size::default();
+        self.max_size = Size::default();
+        self.height_for_width = false;
+        self.baseline = None;
     }
 }
 
 impl Cell {
-    fn cache_min_size(&mut self) {
-        self.min_size = self.element.min_size();
-        self.min_size.width += self.props.padding.left + self.props.padding.right;
-        self.min_size.height += self.props.padding.top + self.props.padding.bottom;
+    fn calc_sizes(&self) -> CellSizes {
+        let mut min_size = self.element.min_size();
+        min_size.width += self.props.padding.left + self.props.padding.right;
+        min_size.height += self.props.padding.top + self.props.padding.bottom;
+
+        let mut max_size = self.element.max_size();
+        max_size.width = std::cmp::min(max_size.width, self.props.max_size.width)
+            .saturating_add(self.props.padding.left + self.props.padding.right);
+        max_size.width = std::cmp::max(max_size.width, min_size.width);
+        max_size.height = std::cmp::min(max_size.height, self.props.max_size.height)
+            .saturating_add(self.props.padding.top + self.props.padding.bottom);
+        max_size.height = std::cmp::max(max_size.height, min_size.height);
+
+        CellSizes {
+            min_size,
+            max_size,
+            height_for_width: self.element.has_height_for_width(),
+            baseline: self
+                .element
+                .baseline()
+                .map(|baseline| baseline + self.props.padding.top),
+            visible: self.element.is_visible(),
+        }
+    }
+
+    fn baseline_for_height(&self, sizes: &CellSizes, height: i32) -> Option<i32> {
+        sizes.baseline?;
+        padded_baseline(&*self.element, &self.props.padding, height)
+    }
+
+    fn min_height_for_width(&self, sizes: &CellSizes, cell_width: i32) -> i32 {
+        if !sizes.height_for_width {
+            return sizes.min_size.height;
+        }
+
+        let (_, widget_width) = calc_widget_bounds(
+            0,
+            cell_width,
+            sizes.min_size.width,
+            sizes.max_size.width,
+            self.props.padding.left,
+            self.props.padding.right,
+            self.props.horz_align,
+        );
+        self.element.min_height_for_width(widget_width)
+            + self.props.padding.top
+            + self.props.padding.bottom
     }
 }
 
-fn collect_stretch_stripes(stripes: &[Stripe], groups: &[StripeProperties]) -> Vec<usize> {
+pub(crate) fn collect_stretch_stripes(
+    stripes: &[Stripe],
+    groups: &[StripeProperties],
+) -> Vec<usize> {
     stripes
         .iter()
         .enumerate()
         .filter_map(
             |(idx, stripe)| {
-                if groups[stripe.group_idx].stretch > 0 {
+                if groups[stripe.group_idx].is_stretch() {
                     Some(idx)
                 } else {
                     None
@@ -296,7 +824,7 @@ fn collect_stretch_stripes(stripes: &[Stripe], groups: &[StripeProperties]) -> V
         .collect()
 }
 
-fn sort_stretch_stripes(
+pub(crate) fn sort_stretch_stripes(
     stripes: &[Stripe],
     groups: &[StripeProperties],
     stretch_stripes: &mut [usize],
@@ -308,34 +836,124 @@ fn sort_stretch_stripes(
     });
 }
 
-fn span_size(stripes: &[Stripe], groups: &[StripeProperties], spacing: i32) -> i32 {
-    if stripes.len() == 0 {
+pub(crate) fn span_size(
+    stripes: &[Stripe],
+    groups: &[StripeProperties],
+    collapsed: &[bool],
+    spacing: i32,
+) -> i32 {
+    let visible = count_visible_stripes(stripes, collapsed);
+    if visible == 0 {
         return 0;
     }
 
     let mut size = stripes
         .iter()
-        .map(|stripe| groups[stripe.group_idx].min_size)
+        .enumerate()
+        .filter(|(idx, _)| !is_collapsed(collapsed, *idx))
+        .map(|(_, stripe)| groups[stripe.group_idx].min_size)
         .sum();
-    size += (stripes.len() as i32 - 1) * spacing;
+    size += (visible - 1) * spacing;
     size
 }
 
+fn span_max_size(
+    stripes: &[Stripe],
+    groups: &[StripeProperties],
+    collapsed: &[bool],
+    spacing: i32,
+) -> i32 {
+    let visible = count_visible_stripes(stripes, collapsed);
+    if visible == 0 {
+        return 0;
+    }
+
+    stripes
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !is_collapsed(collapsed, *idx))
+        .map(|(_, stripe)| {
+            let group = &groups[stripe.group_idx];
+            if group.is_stretch() || group.percentage.is_some() {
+                group.max_size
+            } else {
+                group.min_size
+            }
+        })
+        .fold((visible - 1) * spacing, i32::saturating_add)
+}
+
+fn collect_collapsed_stripes(
+    stripes: &[Stripe],
+    groups: &[StripeProperties],
+    cells: &[CellSizes],
+) -> Vec<bool> {
+    stripes
+        .iter()
+        .map(|stripe| {
+            groups[stripe.group_idx].collapsible
+                && stripe
+                    .cells
+                    .iter()
+                    .filter_map(StripeCell::cell_idx)
+                    .all(|idx| !cells[idx].visible)
+        })
+        .collect()
+}
+
+fn is_collapsed(collapsed: &[bool], idx: usize) -> bool {
+    collapsed.get(idx).copied().unwrap_or(false)
+}
+
+fn count_visible_stripes(stripes: &[Stripe], collapsed: &[bool]) -> i32 {
+    (0..stripes.len())
+        .filter(|&idx| !is_collapsed(collapsed, idx))
+        .count() as i32
+}
+
+fn cache_stripe_max_sizes(
+    stripes: &[Stripe],
+    limits: &[StripeProperties],
+    groups: &mut [StripeProperties],
+    cells: &[CellSizes],
+    cell_max_size: impl Fn(&CellSizes) -> i32,
+) {
+    for stripe in stripes.iter() {
+        groups[stripe.group_idx].max_size = 0;
+    }
+    for stripe in stripes.iter() {
+        let stripe_max_size = stripe
+            .cells
+            .iter()
+            .filter_map(StripeCell::cell_idx)
+            .map(|idx| cell_max_size(&cells[idx]))
+            .max()
+            .unwrap_or(i32::MAX);
+        let group = &mut groups[stripe.group_idx];
+        group.max_size = std::cmp::max(group.max_size, stripe_max_size);
+    }
+    for stripe in stripes.iter() {
+        let group = &mut groups[stripe.group_idx];
+        group.max_size = std::cmp::min(group.max_size, limits[stripe.group_idx].max_size);
+        group.max_size = std::cmp::max(group.max_size, group.min_size);
+    }
+}
+
 fn adjust_span_stripes(
     min_size: i32,
     stripes: &[Stripe],
     groups: &mut [StripeProperties],
     spacing: i32,
 ) {
-    let current_size = span_size(stripes, groups, spacing);
+    let current_size = span_size(stripes, groups, &[], spacing);
     if current_size >= min_size {
         return;
     }
 
     let mut stretch_stripes = collect_stretch_stripes(stripes, groups);
-    if stretch_stripes.len() > 0 {
+    if !stretch_stripes.is_empty() {
         sort_stretch_stripes(stripes, groups, &mut stretch_stripes);
-        let bounds = calc_stripe_bounds(min_size, stripes, groups, &stretch_stripes, spacing);
+        let bounds = calc_stripe_bounds(min_size, stripes, groups, &stretch_stripes, &[], spacing);
         for idx in stretch_stripes {
             groups[stripes[idx].group_idx].min_size = bounds[idx].1;
         }
@@ -344,81 +962,492 @@ fn adjust_span_stripes(
     }
 }
 
-fn calc_stripe_bounds(
+pub(crate) fn calc_stripe_bounds(
     total_size: i32,
     stripes: &[Stripe],
     groups: &[StripeProperties],
     stretch_stripes: &[usize],
+    collapsed: &[bool],
     spacing: i32,
 ) -> Vec<(i32, i32)> {
     let mut bounds = Vec::with_capacity(stripes.len());
 
-    let mut stretch_budget = total_size - (stripes.len() - 1) as i32 * spacing;
-    let mut stretch_count: i32 = 0;
-    for stripe in stripes.iter() {
+    let visible = count_visible_stripes(stripes, collapsed);
+    let content_size = total_size - std::cmp::max(0, visible - 1) * spacing;
+    let mut stretch_budget = content_size;
+    let mut stretch_total = 0.0;
+    let mut required_size = 0;
+    for (idx, stripe) in stripes.iter().enumerate() {
         let group = &groups[stripe.group_idx];
-        if group.stretch == 0 {
-            stretch_budget -= group.min_size;
+        if is_collapsed(collapsed, idx) {
+            bounds.push((0, 0));
+        } else if group.is_stretch() {
+            stretch_total += group.stretch;
+            required_size += group.min_size;
+            bounds.push((0, group.min_size));
         } else {
-            stretch_count += group.stretch as i32;
+            let stripe_size = group.fixed_size(content_size);
+            stretch_budget -= stripe_size;
+            required_size += stripe_size;
+            bounds.push((0, stripe_size));
         }
-        bounds.push((0, group.min_size));
     }
-    stretch_budget = std::cmp::max(0, stretch_budget);
-
-    let mut stretch_unit = if stretch_count > 0 { stretch_budget / stretch_count } else { 0 };
-    for &stripe_idx in stretch_stripes.iter() {
-        let stripe = &stripes[stripe_idx];
-        let group = &groups[stripe.group_idx];
 
-        let factor = group.stretch as i32;
-        stretch_count -= factor;
-        let stripe_size = if stretch_count > 0 { stretch_unit * factor } else { stretch_budget };
+    let mut pending = if required_size > content_size {
+        shrink_stripes(required_size - content_size, stripes, groups, &mut bounds);
+        Vec::new()
+    } else {
+        stretch_stripes
+            .iter()
+            .copied()
+            .filter(|&idx| !is_collapsed(collapsed, idx))
+            .collect()
+    };
+    while !pending.is_empty() {
+        let stretch_unit = stretch_budget as f64 / stretch_total;
 
-        if stripe_size < group.min_size {
-            stretch_budget -= group.min_size;
-            if stretch_count > 0 {
-                stretch_unit = stretch_budget / stretch_count;
+        let mut deficit = 0.0;
+        let mut surplus = 0.0;
+        for &stripe_idx in pending.iter() {
+            let group = &groups[stripes[stripe_idx].group_idx];
+            let stripe_size = stretch_unit * group.stretch;
+            if stripe_size < group.min_size as f64 {
+                deficit += group.min_size as f64 - stripe_size;
+            } else if stripe_size > group.max_size as f64 {
+                surplus += stripe_size - group.max_size as f64;
             }
-        } else {
-            stretch_budget -= stripe_size;
-            bounds[stripe_idx].1 = stripe_size;
         }
+
+        if (deficit == 0.0) && (surplus == 0.0) {
+            distribute_stretch_budget(
+                stretch_budget,
+                stretch_unit,
+                stripes,
+                groups,
+                &pending,
+                &mut bounds,
+            );
+            break;
+        }
+
+        let fix_min = deficit >= surplus;
+        pending.retain(|&stripe_idx| {
+            let group = &groups[stripes[stripe_idx].group_idx];
+            let stripe_size = stretch_unit * group.stretch;
+            let fixed_size = if fix_min && (stripe_size < group.min_size as f64) {
+                group.min_size
+            } else if !fix_min && (stripe_size > group.max_size as f64) {
+                group.max_size
+            } else {
+                return true;
+            };
+            stretch_budget -= fixed_size;
+            stretch_total -= group.stretch;
+            bounds[stripe_idx].1 = fixed_size;
+            false
+        });
     }
 
     let mut start = 0;
-    for stripe_bounds in bounds.iter_mut() {
+    for (idx, stripe_bounds) in bounds.iter_mut().enumerate() {
         stripe_bounds.0 = start;
-        start += stripe_bounds.1 + spacing;
+        if !is_collapsed(collapsed, idx) {
+            start += stripe_bounds.1 + spacing;
+        }
     }
 
     bounds
 }
 
-fn calc_widget_bounds(
-    group_start: i32,
+fn shrink_stripes(
+    deficit: i32,
+    stripes: &[Stripe],
+    groups: &[StripeProperties],
+    bounds: &mut [(i32, i32)],
+) {
+    let mut priorities: Vec<u8> = stripes
+        .iter()
+        .map(|stripe| groups[stripe.group_idx].shrink_priority)
+        .collect();
+    priorities.retain(|&priority| priority > 0);
+    priorities.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+    priorities.dedup();
+
+    let mut deficit = deficit;
+    for priority in priorities {
+        let tier: Vec<usize> = (0..stripes.len())
+            .filter(|&idx| groups[stripes[idx].group_idx].shrink_priority == priority)
+            .collect();
+        let available: i32 = tier.iter().map(|&idx| bounds[idx].1).sum();
+        if available == 0 {
+            continue;
+        }
+
+        let shrink = std::cmp::min(deficit, available);
+        let mut leftover = shrink;
+        for &idx in tier.iter() {
+            let amount = (shrink as i64 * bounds[idx].1 as i64 / available as i64) as i32;
+            bounds[idx].1 -= amount;
+            leftover -= amount;
+        }
+        for &idx in tier.iter() {
+            if leftover == 0 {
+                break;
+            }
+            if bounds[idx].1 > 0 {
+                bounds[idx].1 -= 1;
+                leftover -= 1;
+            }
+        }
+
+        deficit -= shrink;
+        if deficit == 0 {
+            break;
+        }
+    }
+}
+
+fn distribute_stretch_budget(
+    stretch_budget: i32,
+    stretch_unit: f64,
+    stripes: &[Stripe],
+    groups: &[StripeProperties],
+    pending: &[usize],
+    bounds: &mut [(i32, i32)],
+) {
+    let mut remainders = Vec::with_capacity(pending.len());
+    let mut leftover = stretch_budget;
+    for &stripe_idx in pending.iter() {
+        let group = &groups[stripes[stripe_idx].group_idx];
+        let exact_size = stretch_unit * group.stretch;
+        let stripe_size = std::cmp::max(group.min_size, exact_size.floor() as i32);
+        leftover -= stripe_size;
+        bounds[stripe_idx].1 = stripe_size;
+        remainders.push((stripe_idx, exact_size - stripe_size as f64));
+    }
+
+    remainders.sort_by(|(lidx, lrem), (ridx, rrem)| {
+        rrem.partial_cmp(lrem)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(lidx.cmp(ridx))
+    });
+    for (stripe_idx, _) in remainders {
+        if leftover <= 0 {
+            break;
+        }
+        let group = &groups[stripes[stripe_idx].group_idx];
+        if bounds[stripe_idx].1 < group.max_size {
+            bounds[stripe_idx].1 += 1;
+            leftover -= 1;
+        }
+    }
+}
+
+pub(crate) fn baseline_block(blocks: impl Iterator<Item = (i32, i32)>) -> Option<(i32, i32)> {
+    blocks
+        .map(|(baseline, height)| (baseline, std::cmp::max(0, height - baseline)))
+        .reduce(|(lhs_ascent, lhs_descent), (rhs_ascent, rhs_descent)| {
+            (
+                std::cmp::max(lhs_ascent, rhs_ascent),
+                std::cmp::max(lhs_descent, rhs_descent),
+            )
+        })
+}
+
+pub(crate) fn calc_baseline_bounds(
+    block_start: i32,
+    block_size: i32,
+    (ascent, descent): (i32, i32),
+    baseline: i32,
+    height: i32,
+    pad_start: i32,
+    pad_end: i32,
+) -> (i32, i32) {
+    let offset = std::cmp::max(0, (block_size - (ascent + descent)) / 2);
+    let widget_start = block_start + offset + ascent - baseline;
+    (
+        widget_start + pad_start,
+        std::cmp::max(0, height - (pad_start + pad_end)),
+    )
+}
+
+fn padded_baseline(element: &dyn LayoutElement, padding: &Padding, height: i32) -> Option<i32> {
+    element
+        .baseline_for_height(height - (padding.top + padding.bottom))
+        .map(|baseline| baseline + padding.top)
+}
+
+fn span_bounds(bounds: &[(i32, i32)], start: usize, span: usize) -> (i32, i32) {
+    let first = bounds[start].0;
+    let (last_start, last_size) = bounds[start + span - 1];
+    (first, last_start + last_size - first)
+}
+
+pub(crate) fn calc_widget_bounds(
     cell_start: i32,
     cell_size: i32,
     min_size: i32,
+    max_size: i32,
     pad_start: i32,
     pad_end: i32,
     align: CellAlign,
 ) -> (i32, i32) {
     let widget_size = match align {
-        CellAlign::Stretch => cell_size,
-        _ => min_size,
+        CellAlign::Stretch => std::cmp::min(cell_size, max_size),
+        _ => std::cmp::min(cell_size, min_size),
     };
 
-    let widget_size = widget_size - pad_start - pad_end;
-    let cell_size = cell_size - pad_start - pad_end;
+    let widget_size = std::cmp::max(0, widget_size - pad_start - pad_end);
+    let cell_size = std::cmp::max(0, cell_size - pad_start - pad_end);
 
     let widget_start = match align {
         CellAlign::Start => 0,
-        CellAlign::Center => (cell_size - widget_size) / 2,
+        CellAlign::Center | CellAlign::Baseline => (cell_size - widget_size) / 2,
         CellAlign::End => cell_size - widget_size,
         CellAlign::Stretch => 0,
     };
-    let widget_start = group_start + pad_start + cell_start + widget_start;
+    let widget_start = cell_start + pad_start + widget_start;
 
     (widget_start, widget_size)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(stretch: f64, min_size: i32) -> StripeProperties {
+        let mut props = StripeProperties {
+            stretch: 0.0,
+            percentage: None,
+            min_size,
+            max_size: i32::MAX,
+            shrink_priority: 0,
+            collapsible: false,
+        };
+        props.set_stretch(stretch);
+        props
+    }
+
+    fn layout(total_size: i32, groups: &[StripeProperties], spacing: i32) -> Vec<(i32, i32)> {
+        let stripes: Vec<Stripe> = (0..groups.len())
+            .map(|group_idx| Stripe {
+                cells: Vec::new(),
+                group_idx,
+            })
+            .collect();
+        let mut stretch_stripes = collect_stretch_stripes(&stripes, groups);
+        sort_stretch_stripes(&stripes, groups, &mut stretch_stripes);
+        calc_stripe_bounds(total_size, &stripes, groups, &stretch_stripes, &[], spacing)
+    }
+
+    fn sizes(bounds: &[(i32, i32)]) -> Vec<i32> {
+        bounds.iter().map(|&(_, size)| size).collect()
+    }
+
+    #[test]
+    fn splits_ties_towards_earlier_stripes() {
+        let groups = [group(1.0, 0), group(1.0, 0), group(1.0, 0)];
+        assert_eq!(layout(100, &groups, 0), vec![(0, 34), (34, 33), (67, 33)]);
+        assert_eq!(sizes(&layout(101, &groups, 0)), vec![34, 34, 33]);
+    }
+
+    #[test]
+    fn splits_by_fractional_stretch() {
+        let groups = [group(0.5, 0), group(1.5, 0)];
+        assert_eq!(sizes(&layout(100, &groups, 0)), vec![25, 75]);
+    }
+
+    #[test]
+    fn keeps_min_sizes_while_stretching() {
+        let groups = [group(1.0, 80), group(1.0, 0), group(0.0, 10)];
+        assert_eq!(layout(120, &groups, 5), vec![(0, 80), (85, 20), (110, 10)]);
+    }
+
+    #[test]
+    fn honours_max_sizes_while_stretching() {
+        let mut capped = group(1.0, 0);
+        capped.max_size = 10;
+        let groups = [capped, group(1.0, 0)];
+        assert_eq!(sizes(&layout(100, &groups, 0)), vec![10, 90]);
+    }
+
+    #[test]
+    fn treats_infinite_stretch_as_dominant() {
+        let groups = [group(f64::INFINITY, 0), group(1.0, 0)];
+        assert_eq!(sizes(&layout(100, &groups, 0)), vec![100, 0]);
+
+        let groups = [group(f64::INFINITY, 0), group(f64::INFINITY, 0)];
+        assert_eq!(sizes(&layout(100, &groups, 0)), vec![50, 50]);
+    }
+
+    #[test]
+    fn ignores_invalid_stretch() {
+        assert!(!group(f64::NAN, 0).is_stretch());
+        assert!(!group(f64::NEG_INFINITY, 0).is_stretch());
+        assert!(!group(-1.0, 0).is_stretch());
+
+        let groups = [group(f64::NAN, 10), group(1.0, 0)];
+        assert_eq!(sizes(&layout(100, &groups, 0)), vec![10, 90]);
+    }
+
+    #[test]
+    fn clamps_percentage_to_the_content_size() {
+        let mut percent = group(0.0, 0);
+        percent.set_percentage(150.0);
+        assert_eq!(percent.percentage, Some(100.0));
+        assert_eq!(
+            sizes(&layout(200, &[percent, group(1.0, 0)], 0)),
+            vec![200, 0]
+        );
+
+        percent.set_percentage(f64::NAN);
+        assert_eq!(percent.percentage, Some(0.0));
+    }
+
+    #[test]
+    fn sizes_percentages_from_the_content_size() {
+        let mut quarter = group(0.0, 0);
+        quarter.set_percentage(25.0);
+        let groups = [quarter, group(1.0, 0)];
+        assert_eq!(layout(210, &groups, 10), vec![(0, 50), (60, 150)]);
+    }
+
+    fn shrinkable(min_size: i32, priority: u8) -> StripeProperties {
+        let mut props = group(0.0, min_size);
+        props.shrink_priority = priority;
+        props
+    }
+
+    #[test]
+    fn overflows_without_shrink_priority() {
+        let groups = [group(0.0, 60), group(0.0, 60)];
+        assert_eq!(layout(100, &groups, 0), vec![(0, 60), (60, 60)]);
+    }
+
+    #[test]
+    fn shrinks_highest_priority_first() {
+        let groups = [shrinkable(60, 1), shrinkable(60, 2), group(0.0, 20)];
+        assert_eq!(sizes(&layout(100, &groups, 0)), vec![60, 20, 20]);
+        assert_eq!(sizes(&layout(50, &groups, 0)), vec![30, 0, 20]);
+    }
+
+    #[test]
+    fn shrinks_tier_proportionally() {
+        let groups = [shrinkable(30, 1), shrinkable(90, 1)];
+        assert_eq!(sizes(&layout(60, &groups, 0)), vec![15, 45]);
+    }
+
+    #[test]
+    fn keeps_unshrinkable_stripes_when_tiers_are_exhausted() {
+        let groups = [shrinkable(10, 1), group(0.0, 50)];
+        assert_eq!(layout(30, &groups, 5), vec![(0, 0), (5, 50)]);
+    }
+
+    #[test]
+    fn clamps_widget_bounds_to_padding() {
+        assert_eq!(
+            calc_widget_bounds(10, 6, 6, 6, 4, 4, CellAlign::Stretch),
+            (14, 0)
+        );
+        assert_eq!(
+            calc_widget_bounds(10, 6, 6, 6, 4, 4, CellAlign::Center),
+            (14, 0)
+        );
+        assert_eq!(
+            calc_widget_bounds(10, 20, 8, 8, 2, 2, CellAlign::End),
+            (24, 4)
+        );
+    }
+
+    struct FakeElement {
+        min_size: Size,
+        baseline: Option<i32>,
+    }
+
+    impl LayoutElement for FakeElement {
+        fn min_size(&self) -> Size {
+            self.min_size
+        }
+
+        fn baseline(&self) -> Option<i32> {
+            self.baseline
+        }
+
+        fn layout(&self, _x: i32, _y: i32, _width: i32, _height: i32) {}
+
+        fn is_visible(&self) -> bool {
+            true
+        }
+    }
+
+    fn baseline_element(height: i32, baseline: Option<i32>) -> FakeElement {
+        FakeElement {
+            min_size: Size { width: 20, height },
+            baseline,
+        }
+    }
+
+    fn baseline_row(
+        cells: &[(FakeElement, i32)],
+        row_start: i32,
+        row_height: i32,
+    ) -> Vec<Option<(i32, i32)>> {
+        let padding = |top: i32| Padding {
+            top,
+            ..Default::default()
+        };
+        let block = baseline_block(cells.iter().filter_map(|(element, top)| {
+            let height = element.min_size.height + top;
+            Some((padded_baseline(element, &padding(*top), height)?, height))
+        }))
+        .unwrap();
+        cells
+            .iter()
+            .map(|(element, top)| {
+                let height = std::cmp::min(row_height, element.min_size.height + top);
+                let baseline = padded_baseline(element, &padding(*top), height)?;
+                Some(calc_baseline_bounds(
+                    row_start, row_height, block, baseline, height, *top, 0,
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn aligns_cells_on_their_baselines() {
+        let cells = [
+            (baseline_element(20, Some(15)), 0),
+            (baseline_element(10, Some(8)), 0),
+            (baseline_element(30, Some(10)), 4),
+            (baseline_element(40, None), 0),
+        ];
+        let bounds = baseline_row(&cells, 100, 45);
+        assert_eq!(
+            bounds,
+            vec![Some((105, 20)), Some((112, 10)), Some((110, 30)), None]
+        );
+
+        let baselines: Vec<i32> = bounds
+            .iter()
+            .zip([15, 8, 10])
+            .map(|(bounds, baseline)| bounds.unwrap().0 + baseline)
+            .collect();
+        assert_eq!(baselines, vec![120, 120, 120]);
+    }
+
+    #[test]
+    fn baseline_block_tracks_ascent_and_descent() {
+        assert_eq!(
+            baseline_block([(15, 20), (8, 10), (14, 34)].into_iter()),
+            Some((15, 20))
+        );
+        assert_eq!(baseline_block([(12, 8)].into_iter()), Some((12, 0)));
+        assert_eq!(baseline_block(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn baseline_bounds_start_at_the_block_when_the_row_is_tight() {
+        assert_eq!(calc_baseline_bounds(0, 35, (15, 20), 8, 10, 0, 0), (7, 10));
+        assert_eq!(calc_baseline_bounds(0, 30, (15, 20), 15, 20, 0, 0), (0, 20));
+    }
+}
@@ -1,17 +1,26 @@
 use std::borrow::Borrow;
+use std::collections::HashMap;
 
 use fltk::group::Group;
 use fltk::prelude::*;
 
-use crate::{Padding, WrapperFactory};
+use crate::{LayoutNotifier, Padding, WrapperFactory};
 
-use super::{Cell, CellAlign, Grid, GridProperties, StripeCell};
+#[cfg(any(debug_assertions, test))]
+use super::error::GridInvariantError;
+#[cfg(any(debug_assertions, test))]
+use super::validate::GridShape;
+use super::{
+    Cell, CellProperties, Grid, GridError, GridProperties, GridTemplateError, Stripe, StripeCell,
+};
 
 use self::group::StripeGroupBuilder;
+use self::template::{GridArea, GridTemplate};
 
 mod cell;
 mod group;
 mod stripe;
+mod template;
 
 pub use cell::CellBuilder;
 pub use stripe::StripeBuilder;
@@ -19,11 +28,9 @@ pub use stripe::StripeBuilder;
 pub struct GridBuilder<G: GroupExt + Clone = Group, F: Borrow<WrapperFactory> = WrapperFactory> {
     props: GridProperties<G>,
     factory: F,
-    default_cell_padding: Padding,
-    default_row_align: Vec<CellAlign>,
-    default_col_align: Vec<CellAlign>,
     next_row: usize,
     next_col: usize,
+    areas: HashMap<String, GridArea>,
 }
 
 #[derive(Clone, Copy)]
@@ -32,15 +39,15 @@ pub struct StripeGroupRef {
     idx: usize,
 }
 
-#[derive(Clone, Copy)]
-enum StripeKind {
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum StripeKind {
     Row,
     Column,
 }
 
 impl<G: GroupExt + Clone> GridBuilder<G> {
     pub fn new(group: G) -> Self {
-        Self::with_factory(group, WrapperFactory::new())
+        Self::with_factory(group, WrapperFactory::with_defaults())
     }
 }
 
@@ -57,13 +64,16 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> GridBuilder<G, F> {
                 groups: Vec::new(),
                 rows: Vec::new(),
                 cols: Vec::new(),
+                auto_refresh: false,
+                default_cell_padding: Default::default(),
+                default_row_align: Vec::new(),
+                default_col_align: Vec::new(),
+                notifier: LayoutNotifier::new(),
             },
             factory,
-            default_cell_padding: Default::default(),
-            default_row_align: Vec::new(),
-            default_col_align: Vec::new(),
             next_row: 0,
             next_col: 0,
+            areas: HashMap::new(),
         }
     }
 
@@ -107,6 +117,11 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> GridBuilder<G, F> {
         self
     }
 
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
     pub fn with_default_cell_padding(
         mut self,
         left: i32,
@@ -114,7 +129,7 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> GridBuilder<G, F> {
         right: i32,
         bottom: i32,
     ) -> Self {
-        self.default_cell_padding = Padding {
+        self.props.default_cell_padding = Padding {
             left,
             top,
             right,
@@ -131,52 +146,79 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> GridBuilder<G, F> {
         self.props.cols.len()
     }
 
-    pub fn row(&mut self) -> StripeBuilder<G, F> {
-        StripeBuilder::new(self, StripeKind::Row, None)
+    pub fn row(&mut self) -> StripeBuilder<'_, G> {
+        StripeBuilder::new(&mut self.props, StripeKind::Row, None, None)
     }
 
-    pub fn col(&mut self) -> StripeBuilder<G, F> {
-        StripeBuilder::new(self, StripeKind::Column, None)
+    pub fn col(&mut self) -> StripeBuilder<'_, G> {
+        StripeBuilder::new(&mut self.props, StripeKind::Column, None, None)
     }
 
-    pub fn row_group(&mut self) -> StripeGroupBuilder<G, F> {
+    pub fn insert_row(&mut self, row: usize) -> StripeBuilder<'_, G> {
+        StripeBuilder::new(&mut self.props, StripeKind::Row, None, Some(row))
+    }
+
+    pub fn insert_col(&mut self, col: usize) -> StripeBuilder<'_, G> {
+        StripeBuilder::new(&mut self.props, StripeKind::Column, None, Some(col))
+    }
+
+    pub fn edit_row(&mut self, row: usize) -> Result<StripeBuilder<'_, G>, GridError> {
+        self.edit_stripe(StripeKind::Row, row)
+    }
+
+    pub fn edit_col(&mut self, col: usize) -> Result<StripeBuilder<'_, G>, GridError> {
+        self.edit_stripe(StripeKind::Column, col)
+    }
+
+    pub fn row_group(&mut self) -> StripeGroupBuilder<'_, G, F> {
         StripeGroupBuilder::new(self, StripeKind::Row)
     }
 
-    pub fn col_group(&mut self) -> StripeGroupBuilder<G, F> {
+    pub fn col_group(&mut self) -> StripeGroupBuilder<'_, G, F> {
         StripeGroupBuilder::new(self, StripeKind::Column)
     }
 
-    pub fn extend_group(&mut self, group: StripeGroupRef) -> StripeBuilder<G, F> {
-        StripeBuilder::new(self, group.kind, Some(group.idx))
+    pub fn extend_group(&mut self, group: StripeGroupRef) -> StripeBuilder<'_, G> {
+        StripeBuilder::new(&mut self.props, group.kind, Some(group.idx), None)
     }
 
-    pub fn cell(&mut self) -> Option<CellBuilder<G, F>> {
-        let (row, col) = self.next_free_cell()?;
-        Some(CellBuilder::new(self, row, col, 1, 1))
+    pub fn cell(&mut self) -> Result<CellBuilder<'_, G, F>, GridError> {
+        let (row, col) = self.next_free_cell().ok_or(GridError::NoFreeCell)?;
+        Ok(self.cell_builder(row, col, 1, 1))
     }
 
-    pub fn cell_at(&mut self, row: usize, col: usize) -> Option<CellBuilder<G, F>> {
-        if (row >= self.props.rows.len()) && (col >= self.props.cols.len()) {
-            return None;
+    pub fn cell_at(&mut self, row: usize, col: usize) -> Result<CellBuilder<'_, G, F>, GridError> {
+        if (row >= self.props.rows.len()) || (col >= self.props.cols.len()) {
+            return Err(GridError::OutOfBounds {
+                row,
+                col,
+                row_span: 1,
+                col_span: 1,
+            });
         }
         match self.props.rows[row].cells[col] {
-            StripeCell::Free | StripeCell::Skipped => Some(CellBuilder::new(self, row, col, 1, 1)),
-            _ => None,
+            StripeCell::Free | StripeCell::Skipped => Ok(self.cell_builder(row, col, 1, 1)),
+            _ => Err(GridError::Occupied {
+                row,
+                col,
+                by: self.props.occupant(row, col),
+            }),
         }
     }
 
-    pub fn span(&mut self, row_span: usize, col_span: usize) -> Option<CellBuilder<G, F>> {
+    pub fn span(
+        &mut self,
+        row_span: usize,
+        col_span: usize,
+    ) -> Result<CellBuilder<'_, G, F>, GridError> {
         if (row_span == 0) || (col_span == 0) {
-            return None;
+            return Err(GridError::ZeroSpan { row_span, col_span });
         }
 
-        let (row, col) = self.next_free_cell()?;
-        if self.is_span_available(row, col, row_span, col_span, false) {
-            Some(CellBuilder::new(self, row, col, row_span, col_span))
-        } else {
-            None
-        }
+        let (row, col) = self.next_free_cell().ok_or(GridError::NoFreeCell)?;
+        self.props
+            .check_span_available(row, col, row_span, col_span, false)?;
+        Ok(self.cell_builder(row, col, row_span, col_span))
     }
 
     pub fn span_at(
@@ -185,26 +227,97 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> GridBuilder<G, F> {
         col: usize,
         row_span: usize,
         col_span: usize,
-    ) -> Option<CellBuilder<G, F>> {
+    ) -> Result<CellBuilder<'_, G, F>, GridError> {
         if (row_span == 0) || (col_span == 0) {
-            return None;
+            return Err(GridError::ZeroSpan { row_span, col_span });
+        }
+
+        if (row >= self.props.rows.len()) || (col >= self.props.cols.len()) {
+            return Err(GridError::OutOfBounds {
+                row,
+                col,
+                row_span,
+                col_span,
+            });
         }
+        self.props
+            .check_span_available(row, col, row_span, col_span, true)?;
+        Ok(self.cell_builder(row, col, row_span, col_span))
+    }
 
-        if (row >= self.props.rows.len()) && (col >= self.props.cols.len()) {
-            return None;
+    pub fn template(&mut self, template: &str) -> Result<(), GridTemplateError> {
+        let template = GridTemplate::parse(template)?;
+        while self.props.rows.len() < template.rows {
+            self.row().add();
+        }
+        while self.props.cols.len() < template.cols {
+            self.col().add();
         }
-        if self.is_span_available(row, col, row_span, col_span, true) {
-            Some(CellBuilder::new(self, row, col, row_span, col_span))
-        } else {
-            None
+        self.areas.extend(template.areas);
+        Ok(())
+    }
+
+    pub fn area(&mut self, name: &str) -> Result<CellBuilder<'_, G, F>, GridTemplateError> {
+        let area = match self.areas.get(name) {
+            Some(area) => *area,
+            None => {
+                return Err(GridTemplateError::UnknownArea {
+                    name: name.to_string(),
+                })
+            }
+        };
+        Ok(self.span_at(area.row, area.col, area.row_span, area.col_span)?)
+    }
+
+    #[cfg(debug_assertions)]
+    pub fn check_invariants(&self) {
+        if let Err(err) = self.props.validate() {
+            panic!("grid invariant violated: {}", err);
         }
     }
 
     pub fn end(self) -> Grid<G> {
+        #[cfg(debug_assertions)]
+        self.check_invariants();
         self.props.group.end();
         Grid::new(self.props)
     }
 
+    fn edit_stripe(
+        &mut self,
+        kind: StripeKind,
+        idx: usize,
+    ) -> Result<StripeBuilder<'_, G>, GridError> {
+        let (row, col, num_stripes) = match kind {
+            StripeKind::Row => (idx, 0, self.props.rows.len()),
+            StripeKind::Column => (0, idx, self.props.cols.len()),
+        };
+        if idx >= num_stripes {
+            return Err(GridError::OutOfBounds {
+                row,
+                col,
+                row_span: 1,
+                col_span: 1,
+            });
+        }
+        Ok(StripeBuilder::edit(&mut self.props, kind, idx))
+    }
+
+    pub(crate) fn append_row(&mut self) -> usize {
+        self.row().add();
+        self.props.rows.len() - 1
+    }
+
+    pub(crate) fn cell_builder(
+        &mut self,
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+    ) -> CellBuilder<'_, G, F> {
+        CellBuilder::new(&mut self.props, &self.factory, row, col, row_span, col_span)
+    }
+
     fn next_free_cell(&mut self) -> Option<(usize, usize)> {
         let mut row = self.next_row;
         let mut col = self.next_col;
@@ -228,68 +341,382 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> GridBuilder<G, F> {
 
         Some((row, col))
     }
+}
 
-    fn is_span_available(
+impl<G: GroupExt + Clone> GridProperties<G> {
+    pub(super) fn check_span_available(
         &self,
         row: usize,
         col: usize,
         row_span: usize,
         col_span: usize,
         allow_skipped: bool,
-    ) -> bool {
-        let top = row;
-        let bottom = top + row_span;
-        let left = col;
-        let right = left + col_span;
+    ) -> Result<(), GridError> {
+        check_area_available(
+            &self.rows,
+            self.cols.len(),
+            row,
+            col,
+            row_span,
+            col_span,
+            allow_skipped,
+        )
+        .map_err(|err| match err {
+            GridError::Occupied { row, col, .. } => GridError::Occupied {
+                row,
+                col,
+                by: self.occupant(row, col),
+            },
+            err => err,
+        })
+    }
+
+    pub(super) fn occupant(&self, row: usize, col: usize) -> Option<(usize, usize)> {
+        let props = match self.rows[row].cells[col] {
+            StripeCell::Cell(idx) => &self.cells[idx].props,
+            StripeCell::Span => {
+                &self
+                    .spans
+                    .iter()
+                    .find(|span| span.props.contains(row, col))?
+                    .props
+            }
+            StripeCell::Free | StripeCell::Skipped => return None,
+        };
+        Some((props.row, props.col))
+    }
+
+    pub(super) fn add_cell(&mut self, cell: Cell) {
+        self.notifier.adopt(&*cell.element);
+        if (cell.props.row_span > 1) || (cell.props.col_span > 1) {
+            return self.add_span(cell);
+        }
+
+        let row = cell.props.row;
+        let col = cell.props.col;
+
+        let cell_idx = self.cells.len();
+        self.cells.push(cell);
+
+        self.rows[row].cells[col] = StripeCell::Cell(cell_idx);
+        self.cols[col].cells[row] = StripeCell::Cell(cell_idx);
+    }
+
+    pub(super) fn set_area(&mut self, props: &CellProperties, value: StripeCell) {
+        let top = props.row;
+        let bottom = top + props.row_span;
+        let left = props.col;
+        let right = left + props.col_span;
+
+        for row in top..bottom {
+            for col in left..right {
+                self.rows[row].cells[col] = value;
+                self.cols[col].cells[row] = value;
+            }
+        }
+    }
+
+    fn add_span(&mut self, span: Cell) {
+        self.set_area(&span.props, StripeCell::Span);
+        self.spans.push(span);
+    }
+
+    fn shift_cells(&mut self, kind: StripeKind, position: usize, count: usize) {
+        for cell in self.cells.iter_mut() {
+            let (start, _) = cell.props.extent_mut(kind);
+            if *start >= position {
+                *start += count;
+            }
+        }
+
+        let mut straddling = Vec::new();
+        for (idx, span) in self.spans.iter_mut().enumerate() {
+            let (start, len) = span.props.extent_mut(kind);
+            if *start >= position {
+                *start += count;
+            } else if *start + *len > position {
+                *len += count;
+                straddling.push(idx);
+            }
+        }
+        for idx in straddling {
+            let props = self.spans[idx].props;
+            self.set_area(&props, StripeCell::Span);
+        }
+    }
+
+    pub(super) fn remove_stripe(&mut self, kind: StripeKind, idx: usize) {
+        let num_stripes = match kind {
+            StripeKind::Row => self.rows.len(),
+            StripeKind::Column => self.cols.len(),
+        };
+        if idx >= num_stripes {
+            return;
+        }
+
+        for cells in [&mut self.cells, &mut self.spans] {
+            for mut cell in std::mem::take(cells) {
+                if shrink_extent(cell.props.extent_mut(kind), idx) {
+                    cells.push(cell);
+                } else {
+                    delete_cell_widget(cell);
+                }
+            }
+        }
+
+        let (stripes, default_aligns, perpendicular) = match kind {
+            StripeKind::Row => (&mut self.rows, &mut self.default_row_align, &mut self.cols),
+            StripeKind::Column => (&mut self.cols, &mut self.default_col_align, &mut self.rows),
+        };
+        stripes.remove(idx);
+        default_aligns.remove(idx);
+        for perp in perpendicular.iter_mut() {
+            perp.cells.remove(idx);
+        }
 
-        if (bottom > self.props.rows.len()) || (right > self.props.cols.len()) {
+        self.prune_groups();
+        self.rebuild_areas();
+    }
+
+    pub(super) fn clear_cell(&mut self, row: usize, col: usize) -> bool {
+        if (row >= self.rows.len()) || (col >= self.cols.len()) {
             return false;
         }
 
-        for cell_row in top..bottom {
-            for cell_col in left..right {
-                let cell_available = match self.props.rows[cell_row].cells[cell_col] {
-                    StripeCell::Free => true,
-                    StripeCell::Skipped if allow_skipped => true,
-                    _ => false,
-                };
-                if !cell_available {
-                    return false;
+        match self.rows[row].cells[col] {
+            StripeCell::Free => return false,
+            StripeCell::Skipped => {
+                self.rows[row].cells[col] = StripeCell::Free;
+                self.cols[col].cells[row] = StripeCell::Free;
+                return true;
+            }
+            StripeCell::Cell(idx) => delete_cell_widget(self.cells.remove(idx)),
+            StripeCell::Span => {
+                let idx = self
+                    .spans
+                    .iter()
+                    .position(|span| span.props.contains(row, col));
+                if let Some(idx) = idx {
+                    delete_cell_widget(self.spans.remove(idx));
                 }
             }
         }
 
+        self.rebuild_areas();
         true
     }
 
-    fn add_cell(&mut self, cell: Cell) {
-        if (cell.props.row_span > 1) || (cell.props.col_span > 1) {
-            return self.add_span(cell);
+    #[cfg(any(debug_assertions, test))]
+    pub(super) fn validate(&self) -> Result<(), GridInvariantError> {
+        let cells: Vec<CellProperties> = self.cells.iter().map(|cell| cell.props).collect();
+        let spans: Vec<CellProperties> = self.spans.iter().map(|span| span.props).collect();
+        GridShape {
+            rows: &self.rows,
+            cols: &self.cols,
+            groups: &self.groups,
+            cells: &cells,
+            spans: &spans,
+            row_aligns: self.default_row_align.len(),
+            col_aligns: self.default_col_align.len(),
         }
+        .validate()
+    }
 
-        let row = cell.props.row;
-        let col = cell.props.col;
+    fn count_group_stripes(&self, group_idx: usize) -> usize {
+        self.rows
+            .iter()
+            .chain(self.cols.iter())
+            .filter(|stripe| stripe.group_idx == group_idx)
+            .count()
+    }
 
-        let cell_idx = self.props.cells.len();
-        self.props.cells.push(cell);
+    fn prune_groups(&mut self) {
+        let mut used = vec![false; self.groups.len()];
+        for stripe in self.rows.iter().chain(self.cols.iter()) {
+            used[stripe.group_idx] = true;
+        }
+
+        let mut remap = Vec::with_capacity(self.groups.len());
+        let mut next_idx = 0;
+        for &is_used in used.iter() {
+            remap.push(next_idx);
+            if is_used {
+                next_idx += 1;
+            }
+        }
 
-        self.props.rows[row].cells[col] = StripeCell::Cell(cell_idx);
-        self.props.cols[col].cells[row] = StripeCell::Cell(cell_idx);
+        self.groups = std::mem::take(&mut self.groups)
+            .into_iter()
+            .zip(used.iter())
+            .filter_map(|(group, &is_used)| is_used.then_some(group))
+            .collect();
+        for stripe in self.rows.iter_mut().chain(self.cols.iter_mut()) {
+            stripe.group_idx = remap[stripe.group_idx];
+        }
     }
 
-    fn add_span(&mut self, span: Cell) {
-        let top = span.props.row;
-        let bottom = top + span.props.row_span;
-        let left = span.props.col;
-        let right = left + span.props.col_span;
+    fn rebuild_areas(&mut self) {
+        for stripe in self.rows.iter_mut().chain(self.cols.iter_mut()) {
+            for cell in stripe.cells.iter_mut() {
+                if let StripeCell::Cell(_) | StripeCell::Span = cell {
+                    *cell = StripeCell::Free;
+                }
+            }
+        }
+        for cell_idx in 0..self.cells.len() {
+            let props = self.cells[cell_idx].props;
+            self.set_area(&props, StripeCell::Cell(cell_idx));
+        }
+        for span_idx in 0..self.spans.len() {
+            let props = self.spans[span_idx].props;
+            self.set_area(&props, StripeCell::Span);
+        }
+    }
+}
 
-        self.props.spans.push(span);
+impl CellProperties {
+    fn extent_mut(&mut self, kind: StripeKind) -> (&mut usize, &mut usize) {
+        match kind {
+            StripeKind::Row => (&mut self.row, &mut self.row_span),
+            StripeKind::Column => (&mut self.col, &mut self.col_span),
+        }
+    }
 
-        for row in top..bottom {
-            for col in left..right {
-                self.props.rows[row].cells[col] = StripeCell::Span;
-                self.props.cols[col].cells[row] = StripeCell::Span;
+    fn contains(&self, row: usize, col: usize) -> bool {
+        (row >= self.row)
+            && (row < self.row + self.row_span)
+            && (col >= self.col)
+            && (col < self.col + self.col_span)
+    }
+}
+
+fn check_area_available(
+    rows: &[Stripe],
+    num_cols: usize,
+    row: usize,
+    col: usize,
+    row_span: usize,
+    col_span: usize,
+    allow_skipped: bool,
+) -> Result<(), GridError> {
+    let bottom = row + row_span;
+    let right = col + col_span;
+    if (bottom > rows.len()) || (right > num_cols) {
+        return Err(GridError::OutOfBounds {
+            row,
+            col,
+            row_span,
+            col_span,
+        });
+    }
+
+    for (cell_row, stripe) in rows.iter().enumerate().take(bottom).skip(row) {
+        for cell_col in col..right {
+            let cell_available = match stripe.cells[cell_col] {
+                StripeCell::Free => true,
+                StripeCell::Skipped if allow_skipped => true,
+                _ => false,
+            };
+            if !cell_available {
+                return Err(GridError::Occupied {
+                    row: cell_row,
+                    col: cell_col,
+                    by: None,
+                });
             }
         }
     }
+
+    Ok(())
+}
+
+fn shrink_extent((start, len): (&mut usize, &mut usize), removed_idx: usize) -> bool {
+    if *start > removed_idx {
+        *start -= 1;
+    } else if *start + *len > removed_idx {
+        if *len == 1 {
+            return false;
+        }
+        *len -= 1;
+    }
+    true
+}
+
+fn delete_cell_widget(cell: Cell) {
+    if let Some(widget) = cell.widget.or_else(|| cell.element.widget()) {
+        fltk::app::delete_widget(widget);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(cells: &[&[StripeCell]]) -> Vec<Stripe> {
+        cells
+            .iter()
+            .map(|cells| Stripe {
+                cells: cells.to_vec(),
+                group_idx: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn accepts_free_area() {
+        use StripeCell::*;
+        let rows = rows(&[&[Cell(0), Free, Free], &[Free, Free, Free]]);
+        assert_eq!(check_area_available(&rows, 3, 0, 1, 2, 2, false), Ok(()));
+    }
+
+    #[test]
+    fn rejects_overlapping_area() {
+        use StripeCell::*;
+        let rows = rows(&[
+            &[Free, Free, Free],
+            &[Free, Span, Span],
+            &[Free, Span, Span],
+        ]);
+        assert_eq!(
+            check_area_available(&rows, 3, 0, 0, 2, 2, false),
+            Err(GridError::Occupied {
+                row: 1,
+                col: 1,
+                by: None,
+            })
+        );
+        assert_eq!(check_area_available(&rows, 3, 0, 0, 1, 3, false), Ok(()));
+    }
+
+    #[test]
+    fn rejects_skipped_cells_unless_allowed() {
+        use StripeCell::*;
+        let rows = rows(&[&[Free, Skipped]]);
+        assert_eq!(
+            check_area_available(&rows, 2, 0, 0, 1, 2, false),
+            Err(GridError::Occupied {
+                row: 0,
+                col: 1,
+                by: None,
+            })
+        );
+        assert_eq!(check_area_available(&rows, 2, 0, 0, 1, 2, true), Ok(()));
+    }
+
+    #[test]
+    fn rejects_area_outside_the_grid() {
+        let rows = rows(&[
+            &[StripeCell::Free, StripeCell::Free],
+            &[StripeCell::Free, StripeCell::Free],
+        ]);
+        assert_eq!(
+            check_area_available(&rows, 2, 1, 1, 1, 2, false),
+            Err(GridError::OutOfBounds {
+                row: 1,
+                col: 1,
+                row_span: 1,
+                col_span: 2,
+            })
+        );
+    }
 }
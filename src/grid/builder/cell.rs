@@ -1,21 +1,22 @@
 use std::borrow::Borrow;
 use std::rc::Rc;
 
-use fltk::prelude::GroupExt;
+use fltk::prelude::{GroupExt, WidgetExt};
+use fltk::widget::Widget;
 
-use crate::grid::{Cell, CellAlign, CellProperties, Padding, StripeCell};
-use crate::{IntoWidget, LayoutElement, WrapperFactory};
-
-use super::GridBuilder;
+use crate::grid::{Cell, CellAlign, CellProperties, GridProperties, Padding, StripeCell};
+use crate::{IntoWidget, LayoutElement, Size, WrapperFactory};
 
 pub struct CellBuilder<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> {
-    owner: &'l mut GridBuilder<G, F>,
+    owner: &'l mut GridProperties<G>,
+    factory: &'l F,
     props: CellProperties,
 }
 
 impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> CellBuilder<'l, G, F> {
-    pub(super) fn new(
-        owner: &'l mut GridBuilder<G, F>,
+    pub(in crate::grid) fn new(
+        owner: &'l mut GridProperties<G>,
+        factory: &'l F,
         row: usize,
         col: usize,
         row_span: usize,
@@ -26,6 +27,7 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> CellBuilder<'l, G, F> {
         let vert_align = owner.default_row_align[row];
         Self {
             owner,
+            factory,
             props: CellProperties {
                 row,
                 col,
@@ -34,6 +36,7 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> CellBuilder<'l, G, F> {
                 padding,
                 horz_align,
                 vert_align,
+                max_size: Size::UNBOUNDED,
             },
         }
     }
@@ -78,17 +81,23 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> CellBuilder<'l, G, F> {
         self
     }
 
+    pub fn with_max_width(mut self, width: i32) -> Self {
+        self.props.max_size.width = width;
+        self
+    }
+
+    pub fn with_max_height(mut self, height: i32) -> Self {
+        self.props.max_size.height = height;
+        self
+    }
+
+    pub fn with_max_size(mut self, width: i32, height: i32) -> Self {
+        self.props.max_size = Size { width, height };
+        self
+    }
+
     pub fn skip(self) {
-        let top = self.props.row;
-        let bottom = top + self.props.row_span;
-        let left = self.props.col;
-        let right = left + self.props.col_span;
-        for row in top..bottom {
-            for col in left..right {
-                self.owner.props.rows[row].cells[col] = StripeCell::Skipped;
-                self.owner.props.cols[col].cells[row] = StripeCell::Skipped;
-            }
-        }
+        self.owner.set_area(&self.props, StripeCell::Skipped);
     }
 
     pub fn add<E: LayoutElement + 'static>(self, element: E) {
@@ -96,16 +105,46 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> CellBuilder<'l, G, F> {
     }
 
     pub fn add_shared(self, element: Rc<dyn LayoutElement>) {
-        self.owner.add_cell(Cell {
-            element,
-            min_size: Default::default(),
-            props: self.props,
-        });
+        if let Some(widget) = element.widget() {
+            if widget.parent().is_none() {
+                self.owner.group.add(&widget);
+            }
+        }
+        self.add_cell(element, None);
     }
 
     pub fn wrap<W: IntoWidget + 'static>(self, widget: W) -> W {
-        let element = self.owner.factory.borrow().wrap(widget.clone());
-        self.add_shared(element);
+        let element = self.factory.borrow().wrap(widget.clone());
+        self.add_widget(widget, element)
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        let element = Rc::new(wrapper(widget.clone()));
+        self.add_widget(widget, element)
+    }
+
+    fn add_widget<W: IntoWidget + 'static>(self, widget: W, element: Rc<dyn LayoutElement>) -> W {
+        let base = widget.clone().into_widget();
+        let in_group = base
+            .parent()
+            .map(|parent| parent.as_widget_ptr() == self.owner.group.as_widget_ptr())
+            .unwrap_or_default();
+        if !in_group {
+            self.owner.group.add(&base);
+        }
+        self.add_cell(element, Some(base));
         widget
     }
+
+    fn add_cell(self, element: Rc<dyn LayoutElement>, widget: Option<Widget>) {
+        self.owner.add_cell(Cell {
+            element,
+            widget,
+            props: self.props,
+        });
+    }
 }
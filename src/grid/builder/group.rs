@@ -19,14 +19,28 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> StripeGroupBuilder<'l,
             owner,
             kind,
             props: StripeProperties {
-                stretch: 0,
+                stretch: 0.0,
+                percentage: None,
                 min_size: 0,
+                max_size: i32::MAX,
+                shrink_priority: 0,
+                collapsible: false,
             },
         }
     }
 
     pub fn with_stretch(mut self, stretch: u8) -> Self {
-        self.props.stretch = stretch;
+        self.props.stretch = f64::from(stretch);
+        self
+    }
+
+    pub fn with_fractional_stretch(mut self, stretch: f64) -> Self {
+        self.props.set_stretch(stretch);
+        self
+    }
+
+    pub fn with_percentage(mut self, percentage: f64) -> Self {
+        self.props.set_percentage(percentage);
         self
     }
 
@@ -35,6 +49,21 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> StripeGroupBuilder<'l,
         self
     }
 
+    pub fn with_max_size(mut self, max_size: i32) -> Self {
+        self.props.max_size = std::cmp::max(0, max_size);
+        self
+    }
+
+    pub fn with_shrink_priority(mut self, priority: u8) -> Self {
+        self.props.shrink_priority = priority;
+        self
+    }
+
+    pub fn with_collapsible(mut self, collapsible: bool) -> Self {
+        self.props.collapsible = collapsible;
+        self
+    }
+
     pub fn add(self) -> StripeGroupRef {
         let idx = self.owner.props.groups.len();
         self.owner.props.groups.push(self.props);
@@ -1,25 +1,25 @@
-use std::borrow::Borrow;
-
 use fltk::prelude::GroupExt;
 
-use crate::grid::{CellAlign, Stripe, StripeCell, StripeProperties};
-use crate::WrapperFactory;
+use crate::grid::{CellAlign, GridProperties, Stripe, StripeCell, StripeProperties};
 
-use super::{GridBuilder, StripeKind};
+use super::{StripeGroupRef, StripeKind};
 
-pub struct StripeBuilder<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> {
-    owner: &'l mut GridBuilder<G, F>,
+pub struct StripeBuilder<'l, G: GroupExt + Clone> {
+    owner: &'l mut GridProperties<G>,
     kind: StripeKind,
     props: StripeProperties,
     group_idx: Option<usize>,
     default_align: CellAlign,
+    position: Option<usize>,
+    edit: bool,
 }
 
-impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> StripeBuilder<'l, G, F> {
-    pub(super) fn new(
-        owner: &'l mut GridBuilder<G, F>,
+impl<'l, G: GroupExt + Clone> StripeBuilder<'l, G> {
+    pub(in crate::grid) fn new(
+        owner: &'l mut GridProperties<G>,
         kind: StripeKind,
         group_idx: Option<usize>,
+        position: Option<usize>,
     ) -> Self {
         let default_align = match kind {
             StripeKind::Row => CellAlign::Center,
@@ -29,16 +29,53 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> StripeBuilder<'l, G, F>
             owner,
             kind,
             props: StripeProperties {
-                stretch: 0,
+                stretch: 0.0,
+                percentage: None,
                 min_size: 0,
+                max_size: i32::MAX,
+                shrink_priority: 0,
+                collapsible: false,
             },
             group_idx,
             default_align,
+            position,
+            edit: false,
+        }
+    }
+
+    pub(in crate::grid) fn edit(
+        owner: &'l mut GridProperties<G>,
+        kind: StripeKind,
+        idx: usize,
+    ) -> Self {
+        let (stripe, default_align) = match kind {
+            StripeKind::Row => (&owner.rows[idx], owner.default_row_align[idx]),
+            StripeKind::Column => (&owner.cols[idx], owner.default_col_align[idx]),
+        };
+        let props = owner.groups[stripe.group_idx];
+        Self {
+            owner,
+            kind,
+            props,
+            group_idx: None,
+            default_align,
+            position: Some(idx),
+            edit: true,
         }
     }
 
     pub fn with_stretch(mut self, stretch: u8) -> Self {
-        self.props.stretch = stretch;
+        self.props.stretch = f64::from(stretch);
+        self
+    }
+
+    pub fn with_fractional_stretch(mut self, stretch: f64) -> Self {
+        self.props.set_stretch(stretch);
+        self
+    }
+
+    pub fn with_percentage(mut self, percentage: f64) -> Self {
+        self.props.set_percentage(percentage);
         self
     }
 
@@ -47,11 +84,35 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> StripeBuilder<'l, G, F>
         self
     }
 
+    pub fn with_max_size(mut self, max_size: i32) -> Self {
+        self.props.max_size = std::cmp::max(0, max_size);
+        self
+    }
+
+    pub fn with_shrink_priority(mut self, priority: u8) -> Self {
+        self.props.shrink_priority = priority;
+        self
+    }
+
+    pub fn with_collapsible(mut self, collapsible: bool) -> Self {
+        self.props.collapsible = collapsible;
+        self
+    }
+
     pub fn with_default_align(mut self, align: CellAlign) -> Self {
         self.default_align = align;
         self
     }
 
+    pub fn with_group(mut self, group: StripeGroupRef) -> Self {
+        assert!(
+            group.kind == self.kind,
+            "stripe group belongs to the other grid axis"
+        );
+        self.group_idx = Some(group.idx);
+        self
+    }
+
     pub fn add(self) {
         self.add_to_owner(1);
     }
@@ -61,31 +122,79 @@ impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> StripeBuilder<'l, G, F>
     }
 
     fn add_to_owner(self, count: usize) {
+        if self.edit {
+            return self.edit_owner(count);
+        }
         let (stripes, default_aligns, perpendicular) = match self.kind {
             StripeKind::Row => (
-                &mut self.owner.props.rows,
+                &mut self.owner.rows,
                 &mut self.owner.default_row_align,
-                &mut self.owner.props.cols,
+                &mut self.owner.cols,
             ),
             StripeKind::Column => (
-                &mut self.owner.props.cols,
+                &mut self.owner.cols,
                 &mut self.owner.default_col_align,
-                &mut self.owner.props.rows,
+                &mut self.owner.rows,
             ),
         };
-        for _ in 0..count {
+        let position = std::cmp::min(self.position.unwrap_or(stripes.len()), stripes.len());
+        for offset in 0..count {
             let group_idx = self.group_idx.unwrap_or_else(|| {
-                let idx = self.owner.props.groups.len();
-                self.owner.props.groups.push(self.props);
+                let idx = self.owner.groups.len();
+                self.owner.groups.push(self.props);
                 idx
             });
-            stripes.push(Stripe {
-                cells: vec![StripeCell::Free; perpendicular.len()],
-                group_idx,
-            });
-            default_aligns.push(self.default_align);
+            stripes.insert(
+                position + offset,
+                Stripe {
+                    cells: vec![StripeCell::Free; perpendicular.len()],
+                    group_idx,
+                },
+            );
+            default_aligns.insert(position + offset, self.default_align);
             for perp in perpendicular.iter_mut() {
-                perp.cells.push(StripeCell::Free);
+                perp.cells.insert(position + offset, StripeCell::Free);
+            }
+        }
+        if position + count < stripes.len() {
+            self.owner.shift_cells(self.kind, position, count);
+        }
+    }
+
+    fn edit_owner(self, count: usize) {
+        let num_stripes = match self.kind {
+            StripeKind::Row => self.owner.rows.len(),
+            StripeKind::Column => self.owner.cols.len(),
+        };
+        let start = self.position.unwrap_or_default();
+        for idx in start..std::cmp::min(start + count, num_stripes) {
+            let (stripe, default_align) = match self.kind {
+                StripeKind::Row => (
+                    &mut self.owner.rows[idx],
+                    &mut self.owner.default_row_align[idx],
+                ),
+                StripeKind::Column => (
+                    &mut self.owner.cols[idx],
+                    &mut self.owner.default_col_align[idx],
+                ),
+            };
+            *default_align = self.default_align;
+            let current_idx = stripe.group_idx;
+
+            let group_idx = match self.group_idx {
+                Some(group_idx) => group_idx,
+                None if self.owner.count_group_stripes(current_idx) == 1 => {
+                    self.owner.groups[current_idx] = self.props;
+                    current_idx
+                }
+                None => {
+                    self.owner.groups.push(self.props);
+                    self.owner.groups.len() - 1
+                }
+            };
+            match self.kind {
+                StripeKind::Row => self.owner.rows[idx].group_idx = group_idx,
+                StripeKind::Column => self.owner.cols[idx].group_idx = group_idx,
             }
         }
     }
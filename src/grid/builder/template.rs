@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::grid::GridTemplateError;
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct GridArea {
+    pub(super) row: usize,
+    pub(super) col: usize,
+    pub(super) row_span: usize,
+    pub(super) col_span: usize,
+}
+
+pub(super) struct GridTemplate {
+    pub(super) rows: usize,
+    pub(super) cols: usize,
+    pub(super) areas: HashMap<String, GridArea>,
+}
+
+const EMPTY_AREA: &str = ".";
+
+impl GridTemplate {
+    pub(super) fn parse(template: &str) -> Result<Self, GridTemplateError> {
+        let lines: Vec<&str> = if template.contains('"') {
+            template.split('"').skip(1).step_by(2).collect()
+        } else {
+            template.lines().collect()
+        };
+        let rows: Vec<Vec<&str>> = lines
+            .iter()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .filter(|row| !row.is_empty())
+            .collect();
+
+        let cols = rows.first().map(Vec::len).unwrap_or_default();
+        if cols == 0 {
+            return Err(GridTemplateError::EmptyTemplate);
+        }
+        for (row_idx, row) in rows.iter().enumerate() {
+            if row.len() != cols {
+                return Err(GridTemplateError::RaggedTemplate {
+                    row: row_idx,
+                    expected: cols,
+                    found: row.len(),
+                });
+            }
+        }
+
+        let mut areas: HashMap<String, GridArea> = HashMap::new();
+        let mut cell_counts: HashMap<&str, usize> = HashMap::new();
+        for (row_idx, row) in rows.iter().enumerate() {
+            for (col_idx, &name) in row.iter().enumerate() {
+                if name == EMPTY_AREA {
+                    continue;
+                }
+                *cell_counts.entry(name).or_default() += 1;
+                let area = areas.entry(name.to_string()).or_insert(GridArea {
+                    row: row_idx,
+                    col: col_idx,
+                    row_span: 1,
+                    col_span: 1,
+                });
+                let bottom = std::cmp::max(area.row + area.row_span, row_idx + 1);
+                let right = std::cmp::max(area.col + area.col_span, col_idx + 1);
+                area.col = std::cmp::min(area.col, col_idx);
+                area.row_span = bottom - area.row;
+                area.col_span = right - area.col;
+            }
+        }
+
+        for (name, area) in areas.iter() {
+            if area.row_span * area.col_span != cell_counts[name.as_str()] {
+                return Err(GridTemplateError::NonRectangularArea { name: name.clone() });
+            }
+        }
+
+        Ok(Self {
+            rows: rows.len(),
+            cols,
+            areas,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(template: &GridTemplate, name: &str) -> (usize, usize, usize, usize) {
+        let area = template.areas[name];
+        (area.row, area.col, area.row_span, area.col_span)
+    }
+
+    #[test]
+    fn parses_line_template() {
+        let template = GridTemplate::parse(
+            "
+            header header
+            nav    main
+            .      main
+            ",
+        )
+        .unwrap();
+        assert_eq!((template.rows, template.cols), (3, 2));
+        assert_eq!(template.areas.len(), 3);
+        assert_eq!(area(&template, "header"), (0, 0, 1, 2));
+        assert_eq!(area(&template, "nav"), (1, 0, 1, 1));
+        assert_eq!(area(&template, "main"), (1, 1, 2, 1));
+    }
+
+    #[test]
+    fn parses_quoted_template() {
+        let template = GridTemplate::parse(r#""a a b" "c c b""#).unwrap();
+        assert_eq!((template.rows, template.cols), (2, 3));
+        assert_eq!(area(&template, "a"), (0, 0, 1, 2));
+        assert_eq!(area(&template, "b"), (0, 2, 2, 1));
+        assert_eq!(area(&template, "c"), (1, 0, 1, 2));
+    }
+
+    #[test]
+    fn rejects_empty_template() {
+        assert_eq!(
+            GridTemplate::parse("  \n  ").err(),
+            Some(GridTemplateError::EmptyTemplate)
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_template() {
+        assert_eq!(
+            GridTemplate::parse("a b\nc\n").err(),
+            Some(GridTemplateError::RaggedTemplate {
+                row: 1,
+                expected: 2,
+                found: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_l_shaped_area() {
+        assert_eq!(
+            GridTemplate::parse("a a\na b").err(),
+            Some(GridTemplateError::NonRectangularArea {
+                name: "a".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_disjoint_area() {
+        assert_eq!(
+            GridTemplate::parse("a b a").err(),
+            Some(GridTemplateError::NonRectangularArea {
+                name: "a".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_area_grown_leftwards_into_an_l() {
+        assert_eq!(
+            GridTemplate::parse(". a\na a").err(),
+            Some(GridTemplateError::NonRectangularArea {
+                name: "a".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn skips_empty_cells() {
+        let template = GridTemplate::parse(". a\n. a\nb b").unwrap();
+        assert_eq!(template.areas.len(), 2);
+        assert_eq!(area(&template, "a"), (0, 1, 2, 1));
+        assert_eq!(area(&template, "b"), (2, 0, 1, 2));
+    }
+}
@@ -0,0 +1,294 @@
+use std::fmt;
+
+#[cfg(any(debug_assertions, test))]
+use super::StripeCell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridError {
+    OutOfBounds {
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+    },
+    Occupied {
+        row: usize,
+        col: usize,
+        by: Option<(usize, usize)>,
+    },
+    ZeroSpan {
+        row_span: usize,
+        col_span: usize,
+    },
+    NoFreeCell,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridTemplateError {
+    EmptyTemplate,
+    RaggedTemplate {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    NonRectangularArea {
+        name: String,
+    },
+    UnknownArea {
+        name: String,
+    },
+    Grid(GridError),
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds {
+                row,
+                col,
+                row_span,
+                col_span,
+            } => write!(
+                f,
+                "cell at ({}, {}) spanning {}x{} is outside the grid",
+                row, col, row_span, col_span
+            ),
+            Self::Occupied {
+                row,
+                col,
+                by: Some((by_row, by_col)),
+            } => write!(
+                f,
+                "cell at ({}, {}) is occupied by the cell at ({}, {})",
+                row, col, by_row, by_col
+            ),
+            Self::Occupied { row, col, by: None } => {
+                write!(f, "cell at ({}, {}) is skipped", row, col)
+            }
+            Self::ZeroSpan { row_span, col_span } => {
+                write!(f, "cell span {}x{} is empty", row_span, col_span)
+            }
+            Self::NoFreeCell => write!(f, "grid has no free cells left"),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl fmt::Display for GridTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyTemplate => write!(f, "grid template has no cells"),
+            Self::RaggedTemplate {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "grid template row {} has {} columns, expected {}",
+                row, found, expected
+            ),
+            Self::NonRectangularArea { name } => {
+                write!(f, "grid area \"{}\" is not rectangular", name)
+            }
+            Self::UnknownArea { name } => write!(f, "grid area \"{}\" is not defined", name),
+            Self::Grid(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GridTemplateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Grid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<GridError> for GridTemplateError {
+    fn from(err: GridError) -> Self {
+        Self::Grid(err)
+    }
+}
+
+#[cfg(any(debug_assertions, test))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GridInvariantError {
+    RowAlignCount {
+        aligns: usize,
+        rows: usize,
+    },
+    ColAlignCount {
+        aligns: usize,
+        cols: usize,
+    },
+    RowLength {
+        row: usize,
+        cells: usize,
+        cols: usize,
+    },
+    ColLength {
+        col: usize,
+        cells: usize,
+        rows: usize,
+    },
+    MissingRowGroup {
+        row: usize,
+        group: usize,
+    },
+    MissingColGroup {
+        col: usize,
+        group: usize,
+    },
+    MismatchedCell {
+        row: usize,
+        col: usize,
+        row_cell: StripeCell,
+        col_cell: StripeCell,
+    },
+    MissingCell {
+        row: usize,
+        col: usize,
+        idx: usize,
+    },
+    MisplacedCell {
+        row: usize,
+        col: usize,
+        cell_row: usize,
+        cell_col: usize,
+    },
+    SpanningCell {
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+    },
+    CellOutside {
+        row: usize,
+        col: usize,
+    },
+    UnrecordedCell {
+        row: usize,
+        col: usize,
+    },
+    EmptySpan {
+        row: usize,
+        col: usize,
+    },
+    SpanOutside {
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+    },
+    UncoveredSpan {
+        row: usize,
+        col: usize,
+        cell_row: usize,
+        cell_col: usize,
+    },
+    SpanAreaMismatch {
+        marked: usize,
+        covered: usize,
+    },
+}
+
+#[cfg(any(debug_assertions, test))]
+impl fmt::Display for GridInvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::RowAlignCount { aligns, rows } => {
+                write!(f, "{} default row aligns for {} rows", aligns, rows)
+            }
+            Self::ColAlignCount { aligns, cols } => {
+                write!(f, "{} default column aligns for {} columns", aligns, cols)
+            }
+            Self::RowLength { row, cells, cols } => {
+                write!(f, "row {} has {} cells for {} columns", row, cells, cols)
+            }
+            Self::ColLength { col, cells, rows } => {
+                write!(f, "column {} has {} cells for {} rows", col, cells, rows)
+            }
+            Self::MissingRowGroup { row, group } => {
+                write!(f, "row {} refers to missing group {}", row, group)
+            }
+            Self::MissingColGroup { col, group } => {
+                write!(f, "column {} refers to missing group {}", col, group)
+            }
+            Self::MismatchedCell {
+                row,
+                col,
+                row_cell,
+                col_cell,
+            } => write!(
+                f,
+                "cell at ({}, {}) is {:?} in the row but {:?} in the column",
+                row, col, row_cell, col_cell
+            ),
+            Self::MissingCell { row, col, idx } => {
+                write!(
+                    f,
+                    "cell at ({}, {}) refers to missing cell {}",
+                    row, col, idx
+                )
+            }
+            Self::MisplacedCell {
+                row,
+                col,
+                cell_row,
+                cell_col,
+            } => write!(
+                f,
+                "cell at ({}, {}) refers to the cell at ({}, {})",
+                row, col, cell_row, cell_col
+            ),
+            Self::SpanningCell {
+                row,
+                col,
+                row_span,
+                col_span,
+            } => write!(
+                f,
+                "cell at ({}, {}) spans {}x{}",
+                row, col, row_span, col_span
+            ),
+            Self::CellOutside { row, col } => {
+                write!(f, "cell at ({}, {}) is outside the grid", row, col)
+            }
+            Self::UnrecordedCell { row, col } => {
+                write!(
+                    f,
+                    "cell at ({}, {}) is not recorded in its stripes",
+                    row, col
+                )
+            }
+            Self::EmptySpan { row, col } => write!(f, "span at ({}, {}) is empty", row, col),
+            Self::SpanOutside {
+                row,
+                col,
+                row_span,
+                col_span,
+            } => write!(
+                f,
+                "span at ({}, {}) spanning {}x{} is outside the grid",
+                row, col, row_span, col_span
+            ),
+            Self::UncoveredSpan {
+                row,
+                col,
+                cell_row,
+                cell_col,
+            } => write!(
+                f,
+                "span at ({}, {}) does not cover the cell at ({}, {})",
+                row, col, cell_row, cell_col
+            ),
+            Self::SpanAreaMismatch { marked, covered } => write!(
+                f,
+                "{} cells are marked as spanned, but spans cover {}",
+                marked, covered
+            ),
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use super::error::GridInvariantError;
+use super::{CellProperties, Stripe, StripeCell, StripeProperties};
+
+pub(super) struct GridShape<'a> {
+    pub(super) rows: &'a [Stripe],
+    pub(super) cols: &'a [Stripe],
+    pub(super) groups: &'a [StripeProperties],
+    pub(super) cells: &'a [CellProperties],
+    pub(super) spans: &'a [CellProperties],
+    pub(super) row_aligns: usize,
+    pub(super) col_aligns: usize,
+}
+
+impl GridShape<'_> {
+    pub(super) fn validate(&self) -> Result<(), GridInvariantError> {
+        if self.row_aligns != self.rows.len() {
+            return Err(GridInvariantError::RowAlignCount {
+                aligns: self.row_aligns,
+                rows: self.rows.len(),
+            });
+        }
+        if self.col_aligns != self.cols.len() {
+            return Err(GridInvariantError::ColAlignCount {
+                aligns: self.col_aligns,
+                cols: self.cols.len(),
+            });
+        }
+
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            if row.cells.len() != self.cols.len() {
+                return Err(GridInvariantError::RowLength {
+                    row: row_idx,
+                    cells: row.cells.len(),
+                    cols: self.cols.len(),
+                });
+            }
+            if row.group_idx >= self.groups.len() {
+                return Err(GridInvariantError::MissingRowGroup {
+                    row: row_idx,
+                    group: row.group_idx,
+                });
+            }
+        }
+        for (col_idx, col) in self.cols.iter().enumerate() {
+            if col.cells.len() != self.rows.len() {
+                return Err(GridInvariantError::ColLength {
+                    col: col_idx,
+                    cells: col.cells.len(),
+                    rows: self.rows.len(),
+                });
+            }
+            if col.group_idx >= self.groups.len() {
+                return Err(GridInvariantError::MissingColGroup {
+                    col: col_idx,
+                    group: col.group_idx,
+                });
+            }
+        }
+
+        let mut span_area = 0;
+        for row in 0..self.rows.len() {
+            for col in 0..self.cols.len() {
+                let row_cell = self.rows[row].cells[col];
+                let col_cell = self.cols[col].cells[row];
+                if row_cell != col_cell {
+                    return Err(GridInvariantError::MismatchedCell {
+                        row,
+                        col,
+                        row_cell,
+                        col_cell,
+                    });
+                }
+                match row_cell {
+                    StripeCell::Cell(idx) => {
+                        let props = match self.cells.get(idx) {
+                            Some(props) => props,
+                            None => return Err(GridInvariantError::MissingCell { row, col, idx }),
+                        };
+                        if (props.row != row) || (props.col != col) {
+                            return Err(GridInvariantError::MisplacedCell {
+                                row,
+                                col,
+                                cell_row: props.row,
+                                cell_col: props.col,
+                            });
+                        }
+                    }
+                    StripeCell::Span => span_area += 1,
+                    StripeCell::Free | StripeCell::Skipped => (),
+                }
+            }
+        }
+
+        for (idx, props) in self.cells.iter().enumerate() {
+            if (props.row_span != 1) || (props.col_span != 1) {
+                return Err(GridInvariantError::SpanningCell {
+                    row: props.row,
+                    col: props.col,
+                    row_span: props.row_span,
+                    col_span: props.col_span,
+                });
+            }
+            if (props.row >= self.rows.len()) || (props.col >= self.cols.len()) {
+                return Err(GridInvariantError::CellOutside {
+                    row: props.row,
+                    col: props.col,
+                });
+            }
+            if self.rows[props.row].cells[props.col] != StripeCell::Cell(idx) {
+                return Err(GridInvariantError::UnrecordedCell {
+                    row: props.row,
+                    col: props.col,
+                });
+            }
+        }
+
+        let mut spans_area = 0;
+        for props in self.spans.iter() {
+            if (props.row_span == 0) || (props.col_span == 0) {
+                return Err(GridInvariantError::EmptySpan {
+                    row: props.row,
+                    col: props.col,
+                });
+            }
+            if (props.row + props.row_span > self.rows.len())
+                || (props.col + props.col_span > self.cols.len())
+            {
+                return Err(GridInvariantError::SpanOutside {
+                    row: props.row,
+                    col: props.col,
+                    row_span: props.row_span,
+                    col_span: props.col_span,
+                });
+            }
+            for row in props.row..(props.row + props.row_span) {
+                for col in props.col..(props.col + props.col_span) {
+                    if self.rows[row].cells[col] != StripeCell::Span {
+                        return Err(GridInvariantError::UncoveredSpan {
+                            row: props.row,
+                            col: props.col,
+                            cell_row: row,
+                            cell_col: col,
+                        });
+                    }
+                }
+            }
+            spans_area += props.row_span * props.col_span;
+        }
+        if span_area != spans_area {
+            return Err(GridInvariantError::SpanAreaMismatch {
+                marked: span_area,
+                covered: spans_area,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::CellAlign;
+    use crate::{Padding, Size};
+
+    struct Fixture {
+        rows: Vec<Stripe>,
+        cols: Vec<Stripe>,
+        groups: Vec<StripeProperties>,
+        cells: Vec<CellProperties>,
+        spans: Vec<CellProperties>,
+    }
+
+    impl Fixture {
+        fn new(num_rows: usize, num_cols: usize) -> Self {
+            let stripes = |count: usize, len: usize| {
+                (0..count)
+                    .map(|_| Stripe {
+                        cells: vec![StripeCell::Free; len],
+                        group_idx: 0,
+                    })
+                    .collect()
+            };
+            Self {
+                rows: stripes(num_rows, num_cols),
+                cols: stripes(num_cols, num_rows),
+                groups: vec![StripeProperties {
+                    stretch: 0.0,
+                    percentage: None,
+                    min_size: 0,
+                    max_size: i32::MAX,
+                    shrink_priority: 0,
+                    collapsible: false,
+                }],
+                cells: Vec::new(),
+                spans: Vec::new(),
+            }
+        }
+
+        fn mark(&mut self, row: usize, col: usize, cell: StripeCell) {
+            self.rows[row].cells[col] = cell;
+            self.cols[col].cells[row] = cell;
+        }
+
+        fn add_cell(&mut self, row: usize, col: usize) {
+            self.mark(row, col, StripeCell::Cell(self.cells.len()));
+            self.cells.push(cell(row, col, 1, 1));
+        }
+
+        fn add_span(&mut self, row: usize, col: usize, row_span: usize, col_span: usize) {
+            for span_row in row..(row + row_span) {
+                for span_col in col..(col + col_span) {
+                    self.mark(span_row, span_col, StripeCell::Span);
+                }
+            }
+            self.spans.push(cell(row, col, row_span, col_span));
+        }
+
+        fn validate(&self) -> Result<(), GridInvariantError> {
+            GridShape {
+                rows: &self.rows,
+                cols: &self.cols,
+                groups: &self.groups,
+                cells: &self.cells,
+                spans: &self.spans,
+                row_aligns: self.rows.len(),
+                col_aligns: self.cols.len(),
+            }
+            .validate()
+        }
+    }
+
+    fn cell(row: usize, col: usize, row_span: usize, col_span: usize) -> CellProperties {
+        CellProperties {
+            row,
+            col,
+            row_span,
+            col_span,
+            padding: Padding::default(),
+            horz_align: CellAlign::Stretch,
+            vert_align: CellAlign::Stretch,
+            max_size: Size::UNBOUNDED,
+        }
+    }
+
+    #[test]
+    fn accepts_cells_spans_and_skipped_cells() {
+        let mut grid = Fixture::new(3, 3);
+        grid.add_cell(0, 0);
+        grid.add_span(1, 0, 2, 2);
+        grid.mark(0, 2, StripeCell::Skipped);
+        assert_eq!(grid.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_row_and_column_disagreement() {
+        let mut grid = Fixture::new(2, 2);
+        grid.add_cell(1, 1);
+        grid.cols[1].cells[1] = StripeCell::Free;
+        assert_eq!(
+            grid.validate(),
+            Err(GridInvariantError::MismatchedCell {
+                row: 1,
+                col: 1,
+                row_cell: StripeCell::Cell(0),
+                col_cell: StripeCell::Free,
+            })
+        );
+    }
+
+    #[test]
+    fn reports_missing_groups_with_stripe_kind_and_index() {
+        let mut grid = Fixture::new(2, 3);
+        grid.cols[1].group_idx = 1;
+        assert_eq!(
+            grid.validate(),
+            Err(GridInvariantError::MissingColGroup { col: 1, group: 1 })
+        );
+
+        grid.cols[1].group_idx = 0;
+        grid.rows[1].group_idx = 2;
+        assert_eq!(
+            grid.validate(),
+            Err(GridInvariantError::MissingRowGroup { row: 1, group: 2 })
+        );
+    }
+
+    #[test]
+    fn rejects_misplaced_cell() {
+        let mut grid = Fixture::new(2, 2);
+        grid.add_cell(0, 0);
+        grid.cells[0].col = 1;
+        assert_eq!(
+            grid.validate(),
+            Err(GridInvariantError::MisplacedCell {
+                row: 0,
+                col: 0,
+                cell_row: 0,
+                cell_col: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_span_outside_the_grid() {
+        let mut grid = Fixture::new(2, 2);
+        grid.add_span(0, 0, 2, 2);
+        grid.spans[0].col_span = 3;
+        assert_eq!(
+            grid.validate(),
+            Err(GridInvariantError::SpanOutside {
+                row: 0,
+                col: 0,
+                row_span: 2,
+                col_span: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_span_with_uncovered_cell() {
+        let mut grid = Fixture::new(2, 2);
+        grid.add_span(0, 0, 2, 2);
+        grid.mark(1, 1, StripeCell::Free);
+        assert_eq!(
+            grid.validate(),
+            Err(GridInvariantError::UncoveredSpan {
+                row: 0,
+                col: 0,
+                cell_row: 1,
+                cell_col: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_stray_span_marks() {
+        let mut grid = Fixture::new(2, 2);
+        grid.add_span(0, 0, 1, 2);
+        grid.mark(1, 0, StripeCell::Span);
+        assert_eq!(
+            grid.validate(),
+            Err(GridInvariantError::SpanAreaMismatch {
+                marked: 3,
+                covered: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_stripes() {
+        let mut grid = Fixture::new(2, 2);
+        grid.rows[0].cells.pop();
+        assert_eq!(
+            grid.validate(),
+            Err(GridInvariantError::RowLength {
+                row: 0,
+                cells: 1,
+                cols: 2,
+            })
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::ops::{Deref, DerefMut};
 
 use fltk::prelude::*;
+use fltk::widget::Widget;
 
 use super::{LayoutElement, LayoutWidgetWrapper, Size};
 
@@ -31,6 +32,15 @@ impl<I: InputExt + Clone> LayoutElement for InputElement<I> {
         }
     }
 
+    fn baseline(&self) -> Option<i32> {
+        fltk::draw::set_font(self.widget.text_font(), self.widget.text_size());
+        Some(self.widget.frame().dy() + fltk::draw::height() - fltk::draw::descent())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.widget.clone().resize(x, y, width, height)
     }
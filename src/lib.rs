@@ -1,21 +1,74 @@
+use std::rc::Rc;
+
 use fltk::prelude::WidgetExt;
 use fltk::widget::Widget;
 
+pub mod border;
+pub mod browser;
 pub mod button;
+pub mod flex;
+pub mod flow;
+pub mod form;
 pub mod frame;
 pub mod grid;
 pub mod input;
+#[doc(hidden)]
+pub mod macros;
 pub mod misc;
+mod notifier;
 pub mod overlay;
+mod root;
 pub mod scroll;
+pub mod splitter;
+pub mod stack;
+pub mod table;
+pub mod tabs;
 pub mod text;
+pub mod tree;
+pub mod valuator;
 mod wrappers;
 
+pub use self::notifier::LayoutNotifier;
+pub use self::root::LayoutRoot;
 pub use self::wrappers::{SimpleWrapper, WrapperFactory};
 
 pub trait LayoutElement {
     fn min_size(&self) -> Size;
     fn layout(&self, x: i32, y: i32, width: i32, height: i32);
+
+    fn max_size(&self) -> Size {
+        Size::UNBOUNDED
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        false
+    }
+
+    fn min_height_for_width(&self, _width: i32) -> i32 {
+        self.min_size().height
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        None
+    }
+
+    fn baseline_for_height(&self, _height: i32) -> Option<i32> {
+        self.baseline()
+    }
+
+    fn is_visible(&self) -> bool {
+        self.widget().map_or(true, |widget| widget.visible())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        None
+    }
+
+    fn invalidate(&self) {}
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        None
+    }
 }
 
 pub struct EmptyElement;
@@ -34,6 +87,13 @@ pub struct Size {
     pub height: i32,
 }
 
+impl Size {
+    pub const UNBOUNDED: Size = Size {
+        width: i32::MAX,
+        height: i32::MAX,
+    };
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 struct Padding {
     left: i32,
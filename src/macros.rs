@@ -0,0 +1,439 @@
+#[macro_export]
+macro_rules! grid {
+    (
+        $builder:expr;
+        rows [$($row_stretch:literal $({ $($row_key:ident : $row_value:expr),* $(,)? })?),* $(,)?];
+        cols [$($col_stretch:literal $({ $($col_key:ident : $col_value:expr),* $(,)? })?),* $(,)?];
+        $(
+            ($row:literal, $col:literal $(, $row_span:literal, $col_span:literal)?)
+            $([$($cell_key:ident : $cell_value:expr),* $(,)?])?
+            => $kind:ident $(($arg:expr))? $({ $($body:tt)* })?
+        );* $(;)?
+    ) => {{
+        const _: () = $crate::macros::check_grid_cells(
+            $crate::__layout_count!($($row_stretch)*),
+            $crate::__layout_count!($($col_stretch)*),
+            &[$((
+                $row,
+                $col,
+                $crate::__layout_span!($($row_span)?),
+                $crate::__layout_span!($($col_span)?),
+            )),*],
+        );
+
+        let mut builder = $builder;
+        $(
+            let stripe = builder.row().with_stretch($row_stretch);
+            $($(let stripe = $crate::__layout_option!(@stripe stripe, $row_key, $row_value);)*)?
+            stripe.add();
+        )*
+        $(
+            let stripe = builder.col().with_stretch($col_stretch);
+            $($(let stripe = $crate::__layout_option!(@stripe stripe, $col_key, $col_value);)*)?
+            stripe.add();
+        )*
+        $(
+            let cell = builder
+                .span_at(
+                    $row,
+                    $col,
+                    $crate::__layout_span!($($row_span)?),
+                    $crate::__layout_span!($($col_span)?),
+                )
+                .expect("grid cell cannot be placed");
+            $($(let cell = $crate::__layout_option!(@cell cell, $cell_key, $cell_value);)*)?
+            $crate::__layout_place!(cell, $kind $(($arg))? $({ $($body)* })?);
+        )*
+        builder.end()
+    }};
+}
+
+#[macro_export]
+macro_rules! overlay {
+    (
+        $builder:expr;
+        $($kind:ident $(($arg:expr))? $({ $($body:tt)* })?);* $(;)?
+    ) => {{
+        let mut builder = $builder;
+        $($crate::__layout_place!(builder, $kind $(($arg))? $({ $($body)* })?);)*
+        builder.end()
+    }};
+}
+
+#[macro_export]
+macro_rules! scrollable {
+    ($builder:expr; $kind:ident $(($arg:expr))? $({ $($body:tt)* })? $(;)?) => {{
+        let builder = $builder;
+        $crate::__layout_place!(builder, $kind $(($arg))? $({ $($body)* })?)
+    }};
+}
+
+#[macro_export]
+macro_rules! layout {
+    (grid($builder:expr) { $($body:tt)* }) => {
+        $crate::grid!($builder; $($body)*)
+    };
+    (overlay($builder:expr) { $($body:tt)* }) => {
+        $crate::overlay!($builder; $($body)*)
+    };
+    (scrollable($builder:expr) { $($body:tt)* }) => {
+        $crate::scrollable!($builder; $($body)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_place {
+    ($target:ident, grid($builder:expr) { $($body:tt)* }) => {
+        $target.add($crate::grid!($builder; $($body)*))
+    };
+    ($target:ident, overlay($builder:expr) { $($body:tt)* }) => {
+        $target.add($crate::overlay!($builder; $($body)*))
+    };
+    ($target:ident, scrollable($builder:expr) { $($body:tt)* }) => {
+        $target.add($crate::scrollable!($builder; $($body)*))
+    };
+    ($target:ident, $action:ident $(($arg:expr))?) => {
+        $target.$action($($arg)?)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_count {
+    () => { 0usize };
+    ($head:tt $($tail:tt)*) => { 1usize + $crate::__layout_count!($($tail)*) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_span {
+    () => {
+        1usize
+    };
+    ($span:literal) => {
+        $span
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_option {
+    (@stripe $builder:ident, min_size, $value:expr) => {
+        $builder.with_min_size($value)
+    };
+    (@stripe $builder:ident, default_align, $value:expr) => {
+        $builder.with_default_align($value)
+    };
+    (@stripe $builder:ident, max_size, $value:expr) => {
+        $builder.with_max_size($value)
+    };
+    (@stripe $builder:ident, shrink_priority, $value:expr) => {
+        $builder.with_shrink_priority($value)
+    };
+    (@stripe $builder:ident, collapsible, $value:expr) => {
+        $builder.with_collapsible($value)
+    };
+    (@stripe $builder:ident, fractional_stretch, $value:expr) => {
+        $builder.with_fractional_stretch($value)
+    };
+    (@stripe $builder:ident, percentage, $value:expr) => {
+        $builder.with_percentage($value)
+    };
+    (@cell $builder:ident, padding, $value:expr) => {{
+        let (left, top, right, bottom) = $value;
+        $builder.with_padding(left, top, right, bottom)
+    }};
+    (@cell $builder:ident, left_padding, $value:expr) => {
+        $builder.with_left_padding($value)
+    };
+    (@cell $builder:ident, top_padding, $value:expr) => {
+        $builder.with_top_padding($value)
+    };
+    (@cell $builder:ident, right_padding, $value:expr) => {
+        $builder.with_right_padding($value)
+    };
+    (@cell $builder:ident, bottom_padding, $value:expr) => {
+        $builder.with_bottom_padding($value)
+    };
+    (@cell $builder:ident, horz_align, $value:expr) => {
+        $builder.with_horz_align($value)
+    };
+    (@cell $builder:ident, vert_align, $value:expr) => {
+        $builder.with_vert_align($value)
+    };
+    (@cell $builder:ident, max_width, $value:expr) => {
+        $builder.with_max_width($value)
+    };
+    (@cell $builder:ident, max_height, $value:expr) => {
+        $builder.with_max_height($value)
+    };
+    (@cell $builder:ident, max_size, $value:expr) => {{
+        let (width, height) = $value;
+        $builder.with_max_size(width, height)
+    }};
+    (@$kind:ident $builder:ident, $key:ident, $value:expr) => {
+        compile_error!(concat!(
+            "unknown ",
+            stringify!($kind),
+            " option: ",
+            stringify!($key)
+        ))
+    };
+}
+
+#[doc(hidden)]
+pub const fn check_grid_cells(rows: usize, cols: usize, cells: &[(usize, usize, usize, usize)]) {
+    let mut idx = 0;
+    while idx < cells.len() {
+        let (row, col, row_span, col_span) = cells[idx];
+        if (row_span == 0) || (col_span == 0) {
+            panic!("grid cell span must not be empty");
+        }
+        if row + row_span > rows {
+            panic!("grid cell is outside the declared rows");
+        }
+        if col + col_span > cols {
+            panic!("grid cell is outside the declared columns");
+        }
+
+        let mut other_idx = 0;
+        while other_idx < idx {
+            let (other_row, other_col, other_row_span, other_col_span) = cells[other_idx];
+            if (row < other_row + other_row_span)
+                && (other_row < row + row_span)
+                && (col < other_col + other_col_span)
+                && (other_col < col + col_span)
+            {
+                panic!("grid cells overlap");
+            }
+            other_idx += 1;
+        }
+
+        idx += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Display;
+
+    use super::check_grid_cells;
+    use crate::grid::CellAlign;
+
+    #[derive(Default)]
+    struct MockGrid {
+        rows: Vec<(u8, i32)>,
+        cols: Vec<(u8, i32)>,
+        cells: Vec<MockCell>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct MockCell {
+        area: (usize, usize, usize, usize),
+        horz_align: Option<CellAlign>,
+        element: String,
+    }
+
+    struct MockStripeBuilder<'l> {
+        stripes: &'l mut Vec<(u8, i32)>,
+        stretch: u8,
+        min_size: i32,
+    }
+
+    struct MockCellBuilder<'l> {
+        owner: &'l mut MockGrid,
+        cell: MockCell,
+    }
+
+    #[derive(Default)]
+    struct MockOverlay {
+        children: Vec<String>,
+    }
+
+    struct MockScrollable;
+
+    impl MockGrid {
+        fn row(&mut self) -> MockStripeBuilder<'_> {
+            MockStripeBuilder::new(&mut self.rows)
+        }
+
+        fn col(&mut self) -> MockStripeBuilder<'_> {
+            MockStripeBuilder::new(&mut self.cols)
+        }
+
+        fn span_at(
+            &mut self,
+            row: usize,
+            col: usize,
+            row_span: usize,
+            col_span: usize,
+        ) -> Result<MockCellBuilder<'_>, ()> {
+            Ok(MockCellBuilder {
+                owner: self,
+                cell: MockCell {
+                    area: (row, col, row_span, col_span),
+                    horz_align: None,
+                    element: String::new(),
+                },
+            })
+        }
+
+        fn end(self) -> Self {
+            self
+        }
+    }
+
+    impl Display for MockGrid {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "grid({}x{})", self.rows.len(), self.cols.len())
+        }
+    }
+
+    impl<'l> MockStripeBuilder<'l> {
+        fn new(stripes: &'l mut Vec<(u8, i32)>) -> Self {
+            Self {
+                stripes,
+                stretch: 0,
+                min_size: 0,
+            }
+        }
+
+        fn with_stretch(mut self, stretch: u8) -> Self {
+            self.stretch = stretch;
+            self
+        }
+
+        fn with_min_size(mut self, min_size: i32) -> Self {
+            self.min_size = min_size;
+            self
+        }
+
+        fn add(self) {
+            self.stripes.push((self.stretch, self.min_size));
+        }
+    }
+
+    impl MockCellBuilder<'_> {
+        fn with_horz_align(mut self, align: CellAlign) -> Self {
+            self.cell.horz_align = Some(align);
+            self
+        }
+
+        fn add<E: Display>(mut self, element: E) {
+            self.cell.element = element.to_string();
+            self.owner.cells.push(self.cell);
+        }
+
+        fn skip(mut self) {
+            self.cell.element = "skipped".to_string();
+            self.owner.cells.push(self.cell);
+        }
+    }
+
+    impl MockOverlay {
+        fn add<E: Display>(&mut self, element: E) {
+            self.children.push(element.to_string());
+        }
+
+        fn end(self) -> String {
+            format!("overlay({})", self.children.join(", "))
+        }
+    }
+
+    impl MockScrollable {
+        fn add<E: Display>(self, element: E) -> String {
+            format!("scrollable({})", element)
+        }
+    }
+
+    fn cell(area: (usize, usize, usize, usize), element: &str) -> MockCell {
+        MockCell {
+            area,
+            horz_align: None,
+            element: element.to_string(),
+        }
+    }
+
+    #[test]
+    fn grid_places_cells() {
+        let grid = crate::grid!(
+            MockGrid::default();
+            rows [0, 1 { min_size: 10 }];
+            cols [1, 2];
+            (0, 0) [horz_align: CellAlign::Start] => add("title");
+            (0, 1) => skip;
+            (1, 0, 1, 2) => add("body");
+        );
+
+        assert_eq!(grid.rows, vec![(0, 0), (1, 10)]);
+        assert_eq!(grid.cols, vec![(1, 0), (2, 0)]);
+        assert_eq!(
+            grid.cells,
+            vec![
+                MockCell {
+                    horz_align: Some(CellAlign::Start),
+                    ..cell((0, 0, 1, 1), "title")
+                },
+                cell((0, 1, 1, 1), "skipped"),
+                cell((1, 0, 1, 2), "body"),
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_nests_containers() {
+        let grid = crate::layout!(grid(MockGrid::default()) {
+            rows [0, 1];
+            cols [1];
+            (0, 0) => grid(MockGrid::default()) {
+                rows [0];
+                cols [0, 0];
+                (0, 0) => add("left");
+                (0, 1) => add("right")
+            };
+            (1, 0) => overlay(MockOverlay::default()) {
+                add("back");
+                scrollable(MockScrollable) { add("front") }
+            }
+        });
+
+        assert_eq!(
+            grid.cells,
+            vec![
+                cell((0, 0, 1, 1), "grid(1x2)"),
+                cell((1, 0, 1, 1), "overlay(back, scrollable(front))"),
+            ]
+        );
+    }
+
+    #[test]
+    fn valid_cells_pass() {
+        check_grid_cells(2, 3, &[(0, 0, 1, 1), (0, 1, 1, 2), (1, 0, 1, 3)]);
+        check_grid_cells(0, 0, &[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "grid cells overlap")]
+    fn overlapping_cells_fail() {
+        check_grid_cells(2, 2, &[(0, 0, 2, 1), (1, 0, 1, 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the declared rows")]
+    fn cells_below_rows_fail() {
+        check_grid_cells(2, 2, &[(1, 0, 2, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the declared columns")]
+    fn cells_beyond_cols_fail() {
+        check_grid_cells(2, 2, &[(0, 2, 1, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "span must not be empty")]
+    fn empty_spans_fail() {
+        check_grid_cells(2, 2, &[(0, 0, 0, 1)]);
+    }
+}
@@ -1,10 +1,79 @@
 use std::ops::{Deref, DerefMut};
 
+use fltk::menu::Choice;
 use fltk::misc::InputChoice;
 use fltk::prelude::*;
+use fltk::widget::Widget;
 
 use super::{LayoutElement, LayoutWidgetWrapper, Size};
 
+const CHOICE_MAX_ARROW_WIDTH: i32 = 20;
+
+pub struct ChoiceElement {
+    widget: Choice,
+}
+
+impl LayoutWidgetWrapper<Choice> for ChoiceElement {
+    fn wrap(widget: Choice) -> Self {
+        Self { widget }
+    }
+}
+
+impl LayoutElement for ChoiceElement {
+    fn min_size(&self) -> Size {
+        fltk::draw::set_font(self.widget.text_font(), self.widget.text_size());
+        let text_height = fltk::draw::height();
+        let text_width = self
+            .widget
+            .clone()
+            .into_iter()
+            .filter_map(|item| item.label())
+            .map(|label| fltk::draw::measure(&label, true).0)
+            .max()
+            .unwrap_or_default();
+
+        let frame = self.widget.frame();
+        let frame_dx = frame.dx();
+        let frame_dy = frame.dy();
+        let frame_dw = frame.dw();
+        let frame_dh = frame.dh();
+        let frame_width = frame_dx + frame_dw;
+        let frame_height = frame_dy + frame_dh;
+        let arrow_width = std::cmp::min(CHOICE_MAX_ARROW_WIDTH, text_height + 1);
+
+        Size {
+            width: 3 * frame_width + text_width + arrow_width,
+            height: text_height + frame_height + 1,
+        }
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        fltk::draw::set_font(self.widget.text_font(), self.widget.text_size());
+        Some(self.widget.frame().dy() + fltk::draw::height() - fltk::draw::descent())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl Deref for ChoiceElement {
+    type Target = Choice;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for ChoiceElement {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
 pub struct InputChoiceElement {
     widget: InputChoice,
 }
@@ -42,6 +111,15 @@ impl LayoutElement for InputChoiceElement {
         }
     }
 
+    fn baseline(&self) -> Option<i32> {
+        fltk::draw::set_font(self.widget.text_font(), self.widget.text_size());
+        Some(self.widget.frame().dy() + fltk::draw::height() - fltk::draw::descent())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.widget.clone().resize(x, y, width, height);
     }
@@ -0,0 +1,82 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use super::LayoutElement;
+
+#[derive(Default)]
+pub struct LayoutNotifier {
+    stale: Cell<bool>,
+    auto_refresh: Cell<bool>,
+    parent: RefCell<Option<Rc<LayoutNotifier>>>,
+    root: RefCell<Option<Rc<dyn Fn()>>>,
+}
+
+impl LayoutNotifier {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self::default())
+    }
+
+    pub fn adopt(self: &Rc<Self>, child: &dyn LayoutElement) {
+        if let Some(notifier) = child.notifier() {
+            notifier.set_parent(Rc::clone(self));
+        }
+    }
+
+    pub fn set_parent(&self, parent: Rc<LayoutNotifier>) {
+        if self.auto_refresh.get() {
+            parent.enable_auto_refresh();
+        }
+        *self.parent.borrow_mut() = Some(parent);
+    }
+
+    pub(crate) fn set_root(&self, root: Rc<dyn Fn()>) {
+        *self.root.borrow_mut() = Some(root);
+    }
+
+    pub fn enable_auto_refresh(&self) {
+        self.auto_refresh.set(true);
+        if let Some(parent) = self.parent.borrow().as_ref() {
+            parent.enable_auto_refresh();
+        }
+    }
+
+    pub fn auto_refresh(&self) -> bool {
+        self.auto_refresh.get()
+    }
+
+    pub fn should_auto_refresh(&self) -> bool {
+        self.auto_refresh.get() && self.parent.borrow().is_none() && self.root.borrow().is_none()
+    }
+
+    pub fn notify(&self) -> bool {
+        self.stale.set(true);
+        self.notify_parent()
+    }
+
+    pub fn notify_parent(&self) -> bool {
+        let parent = self.parent.borrow().clone();
+        if let Some(parent) = parent {
+            return parent.notify();
+        }
+        let root = self.root.borrow().clone();
+        match root {
+            Some(root) => {
+                root();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn mark_ancestors_stale(&self) {
+        let mut parent = self.parent.borrow().clone();
+        while let Some(notifier) = parent {
+            notifier.stale.set(true);
+            parent = notifier.parent.borrow().clone();
+        }
+    }
+
+    pub fn take_stale(&self) -> bool {
+        self.stale.replace(false)
+    }
+}
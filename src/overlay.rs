@@ -1,14 +1,16 @@
 use std::borrow::Borrow;
+use std::cell::{Ref, RefCell};
 use std::rc::Rc;
 
 use fltk::group::Group;
 use fltk::prelude::{GroupExt, WidgetBase};
+use fltk::widget::Widget;
 
-use crate::{IntoWidget, LayoutElement, Padding, Size, WrapperFactory};
+use crate::{IntoWidget, LayoutElement, LayoutNotifier, Padding, Size, WrapperFactory};
 
 pub struct Overlay<G: GroupExt + Clone = Group> {
     props: OverlayProperties<G>,
-    min_size: Size,
+    cache: RefCell<OverlayCache>,
 }
 
 pub struct OverlayBuilder<G: GroupExt + Clone = Group, F: Borrow<WrapperFactory> = WrapperFactory> {
@@ -20,11 +22,62 @@ struct OverlayProperties<G: GroupExt + Clone> {
     group: G,
     padding: Padding,
     children: Vec<Rc<dyn LayoutElement>>,
+    auto_refresh: bool,
+    notifier: Rc<LayoutNotifier>,
+}
+
+struct OverlayCache {
+    min_size: Size,
+    max_size: Size,
+    height_for_width: bool,
 }
 
 impl<G: GroupExt + Clone> LayoutElement for Overlay<G> {
     fn min_size(&self) -> Size {
-        self.min_size
+        self.cache().min_size
+    }
+
+    fn max_size(&self) -> Size {
+        self.cache().max_size
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        self.cache().height_for_width
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        if !self.has_height_for_width() {
+            return self.min_size().height;
+        }
+
+        let width = width - (self.props.padding.left + self.props.padding.right);
+        let height = self
+            .props
+            .children
+            .iter()
+            .map(|child| {
+                let child_width = std::cmp::min(width, child.max_size().width);
+                child.min_height_for_width(child_width)
+            })
+            .max()
+            .unwrap_or_default();
+        height + self.props.padding.top + self.props.padding.bottom
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        for child in self.props.children.iter() {
+            child.invalidate();
+        }
+        self.props.notifier.take_stale();
+        *self.cache.borrow_mut() = OverlayCache::new(&self.props);
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.props.notifier))
     }
 
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
@@ -48,7 +101,18 @@ impl<G: GroupExt + Clone> Overlay<G> {
         self.props.group.clone()
     }
 
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
     pub fn layout_children(&self) {
+        if self.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+
         let x = self.props.group.x() + self.props.padding.left;
         let y = self.props.group.y() + self.props.padding.top;
         let width = self.props.group.width() - (self.props.padding.left + self.props.padding.right);
@@ -56,11 +120,31 @@ impl<G: GroupExt + Clone> Overlay<G> {
             self.props.group.height() - (self.props.padding.top + self.props.padding.bottom);
 
         for child in self.props.children.iter() {
-            child.layout(x, y, width, height);
+            let max_size = child.max_size();
+            let child_width = std::cmp::min(width, max_size.width);
+            let child_height = std::cmp::min(height, max_size.height);
+            child.layout(x, y, child_width, child_height);
         }
     }
 
     fn new(props: OverlayProperties<G>) -> Self {
+        if props.auto_refresh {
+            props.notifier.enable_auto_refresh();
+        }
+        let cache = RefCell::new(OverlayCache::new(&props));
+        Self { props, cache }
+    }
+
+    fn cache(&self) -> Ref<'_, OverlayCache> {
+        if self.props.notifier.take_stale() {
+            *self.cache.borrow_mut() = OverlayCache::new(&self.props);
+        }
+        self.cache.borrow()
+    }
+}
+
+impl OverlayCache {
+    fn new<G: GroupExt + Clone>(props: &OverlayProperties<G>) -> Self {
         let mut min_size = props.children.iter().map(|child| child.min_size()).fold(
             Default::default(),
             |lhs: Size, rhs: Size| Size {
@@ -71,13 +155,38 @@ impl<G: GroupExt + Clone> Overlay<G> {
         min_size.width += props.padding.left + props.padding.right;
         min_size.height += props.padding.top + props.padding.bottom;
 
-        Self { props, min_size }
+        let mut max_size = props.children.iter().map(|child| child.max_size()).fold(
+            Default::default(),
+            |lhs: Size, rhs: Size| Size {
+                width: std::cmp::max(lhs.width, rhs.width),
+                height: std::cmp::max(lhs.height, rhs.height),
+            },
+        );
+        max_size.width = max_size
+            .width
+            .saturating_add(props.padding.left + props.padding.right);
+        max_size.height = max_size
+            .height
+            .saturating_add(props.padding.top + props.padding.bottom);
+        max_size.width = std::cmp::max(max_size.width, min_size.width);
+        max_size.height = std::cmp::max(max_size.height, min_size.height);
+
+        let height_for_width = props
+            .children
+            .iter()
+            .any(|child| child.has_height_for_width());
+
+        Self {
+            min_size,
+            max_size,
+            height_for_width,
+        }
     }
 }
 
 impl<G: GroupExt + Clone> OverlayBuilder<G> {
     pub fn new(group: G) -> Self {
-        Self::with_factory(group, WrapperFactory::new())
+        Self::with_factory(group, WrapperFactory::with_defaults())
     }
 }
 
@@ -88,6 +197,8 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> OverlayBuilder<G, F> {
                 group,
                 padding: Default::default(),
                 children: Vec::new(),
+                auto_refresh: false,
+                notifier: LayoutNotifier::new(),
             },
             factory,
         }
@@ -123,11 +234,17 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> OverlayBuilder<G, F> {
         self
     }
 
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
     pub fn add<E: LayoutElement + 'static>(&mut self, element: E) {
         self.add_shared(Rc::new(element));
     }
 
     pub fn add_shared(&mut self, element: Rc<dyn LayoutElement>) {
+        self.props.notifier.adopt(&*element);
         self.props.children.push(element);
     }
 
@@ -137,6 +254,15 @@ impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> OverlayBuilder<G, F> {
         widget
     }
 
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.add_shared(Rc::new(wrapper(widget.clone())));
+        widget
+    }
+
     pub fn end(self) -> Overlay<G> {
         self.props.group.end();
         Overlay::new(self.props)
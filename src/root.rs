@@ -0,0 +1,169 @@
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use fltk::prelude::*;
+use fltk::window::Window;
+
+use super::{LayoutElement, Size};
+
+type ResizeCallback<W> = Rc<RefCell<Option<Box<dyn FnMut(&mut W, i32, i32, i32, i32)>>>>;
+
+pub struct LayoutRoot<W: WindowExt + WidgetBase + Clone = Window> {
+    window: W,
+    element: Rc<dyn LayoutElement>,
+    resize_callback: ResizeCallback<W>,
+    resizing: Rc<Cell<bool>>,
+}
+
+struct WeakLayoutRoot<W: WindowExt + WidgetBase + Clone> {
+    window: W,
+    element: Weak<dyn LayoutElement>,
+    resize_callback: ResizeCallback<W>,
+    resizing: Rc<Cell<bool>>,
+}
+
+impl<W: WindowExt + WidgetBase + Clone + 'static> LayoutRoot<W> {
+    pub fn attach<E: LayoutElement + 'static>(window: W, element: E) -> Self {
+        Self::attach_shared(window, Rc::new(element))
+    }
+
+    pub fn attach_shared(mut window: W, element: Rc<dyn LayoutElement>) -> Self {
+        let root = Self {
+            window: window.clone(),
+            element,
+            resize_callback: Rc::new(RefCell::new(None)),
+            resizing: Rc::new(Cell::new(false)),
+        };
+
+        let notifier = root.element.notifier();
+        if let Some(notifier) = notifier.as_ref() {
+            let weak_root = root.downgrade();
+            notifier.set_root(Rc::new(move || {
+                if let Some(root) = weak_root.upgrade() {
+                    let resized = root.update_size_range();
+                    root.layout();
+                    if resized {
+                        root.notify_resized(&mut root.window());
+                    }
+                }
+            }));
+        }
+
+        // The window only holds a weak reference, so the element lives as long as the returned
+        // root and its clones.
+        let weak_root = root.downgrade();
+        window.resize_callback(move |window, _, _, _, _| {
+            let Some(root) = weak_root.upgrade() else {
+                return;
+            };
+            if root.resizing.get() {
+                return;
+            }
+            if notifier
+                .as_ref()
+                .is_some_and(|notifier| notifier.auto_refresh())
+            {
+                root.refresh();
+            } else {
+                if root.element.has_height_for_width() {
+                    root.update_size_range();
+                }
+                root.layout();
+            }
+            root.notify_resized(window);
+        });
+
+        root.update_size_range();
+        root.layout();
+        root
+    }
+
+    pub fn window(&self) -> W {
+        self.window.clone()
+    }
+
+    pub fn element(&self) -> Rc<dyn LayoutElement> {
+        Rc::clone(&self.element)
+    }
+
+    pub fn set_resize_callback<F: FnMut(&mut W, i32, i32, i32, i32) + 'static>(&self, callback: F) {
+        *self.resize_callback.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn refresh(&self) {
+        self.element.invalidate();
+        self.update_size_range();
+        self.layout();
+    }
+
+    pub fn layout(&self) {
+        self.element
+            .layout(0, 0, self.window.width(), self.window.height());
+    }
+
+    fn downgrade(&self) -> WeakLayoutRoot<W> {
+        WeakLayoutRoot {
+            window: self.window.clone(),
+            element: Rc::downgrade(&self.element),
+            resize_callback: Rc::clone(&self.resize_callback),
+            resizing: Rc::clone(&self.resizing),
+        }
+    }
+
+    fn notify_resized(&self, window: &mut W) {
+        if let Some(callback) = self.resize_callback.borrow_mut().as_mut() {
+            let (x, y, w, h) = (window.x(), window.y(), window.width(), window.height());
+            callback(window, x, y, w, h);
+        }
+    }
+
+    fn update_size_range(&self) -> bool {
+        let mut min_size = self.element.min_size();
+        if self.element.has_height_for_width() {
+            let width = std::cmp::max(self.window.width(), min_size.width);
+            min_size.height = self.element.min_height_for_width(width);
+        }
+        let max_size = self.element.max_size();
+        let max_width = if max_size.width == Size::UNBOUNDED.width { 0 } else { max_size.width };
+        let max_height =
+            if max_size.height == Size::UNBOUNDED.height { 0 } else { max_size.height };
+
+        let mut window = self.window.clone();
+        window.size_range(min_size.width, min_size.height, max_width, max_height);
+
+        let width = std::cmp::max(window.width(), min_size.width);
+        let height = std::cmp::max(window.height(), min_size.height);
+        if (width == window.width()) && (height == window.height()) {
+            return false;
+        }
+
+        // Setting the size fires the window's resize callback, which must not lay out the
+        // element again while it is being resized from here.
+        self.resizing.set(true);
+        window.set_size(width, height);
+        self.resizing.set(false);
+        true
+    }
+}
+
+impl<W: WindowExt + WidgetBase + Clone> Clone for LayoutRoot<W> {
+    fn clone(&self) -> Self {
+        Self {
+            window: self.window.clone(),
+            element: Rc::clone(&self.element),
+            resize_callback: Rc::clone(&self.resize_callback),
+            resizing: Rc::clone(&self.resizing),
+        }
+    }
+}
+
+impl<W: WindowExt + WidgetBase + Clone> WeakLayoutRoot<W> {
+    fn upgrade(&self) -> Option<LayoutRoot<W>> {
+        Some(LayoutRoot {
+            window: self.window.clone(),
+            element: self.element.upgrade()?,
+            resize_callback: Rc::clone(&self.resize_callback),
+            resizing: Rc::clone(&self.resizing),
+        })
+    }
+}
@@ -2,8 +2,9 @@ use std::rc::Rc;
 
 use fltk::group::Scroll;
 use fltk::prelude::{GroupExt, WidgetBase};
+use fltk::widget::Widget;
 
-use crate::{LayoutElement, Size};
+use crate::{LayoutElement, LayoutNotifier, Size};
 
 pub struct Scrollable<G: GroupExt + Clone = Scroll> {
     props: ScrollableProperties<G>,
@@ -19,6 +20,8 @@ struct ScrollableProperties<G: GroupExt + Clone> {
     mode: ScrollMode,
     horz_gap: i32,
     vert_gap: i32,
+    auto_refresh: bool,
+    notifier: Rc<LayoutNotifier>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -43,6 +46,44 @@ impl<G: GroupExt + Clone> LayoutElement for Scrollable<G> {
         min_size
     }
 
+    fn max_size(&self) -> Size {
+        let scrollbar_size = fltk::app::scrollbar_size();
+        let mut max_size = self.child.max_size();
+        max_size.width = max_size
+            .width
+            .saturating_add(scrollbar_size + self.props.horz_gap);
+        max_size.height = max_size
+            .height
+            .saturating_add(scrollbar_size + self.props.vert_gap);
+        max_size
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        (self.props.mode == ScrollMode::Horizontal) && self.child.has_height_for_width()
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        if !self.has_height_for_width() {
+            return self.min_size().height;
+        }
+        let child_height =
+            self.child_min_height(width, self.child.min_size(), self.child.max_size());
+        child_height + fltk::app::scrollbar_size() + self.props.vert_gap
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        self.props.notifier.take_stale();
+        self.child.invalidate();
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.props.notifier))
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.props.group.clone().resize(x, y, width, height);
         self.layout_children();
@@ -60,7 +101,18 @@ impl<G: GroupExt + Clone> Scrollable<G> {
         self.props.group.clone()
     }
 
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
     pub fn layout_children(&self) {
+        if self.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+
         let x = self.props.group.x();
         let y = self.props.group.y();
         let mut width = self.props.group.width();
@@ -68,9 +120,10 @@ impl<G: GroupExt + Clone> Scrollable<G> {
 
         let scrollbar_size = fltk::app::scrollbar_size();
         let child_min_size = self.child.min_size();
+        let child_max_size = self.child.max_size();
 
         let horz_scroll = width < child_min_size.width;
-        let vert_scroll = height < child_min_size.height;
+        let vert_scroll = height < self.child_min_height(width, child_min_size, child_max_size);
 
         if horz_scroll {
             height -= scrollbar_size + self.props.vert_gap;
@@ -79,13 +132,34 @@ impl<G: GroupExt + Clone> Scrollable<G> {
             width -= scrollbar_size + self.props.horz_gap;
         }
 
-        width = std::cmp::max(width, child_min_size.width);
-        height = std::cmp::max(height, child_min_size.height);
+        width = std::cmp::max(
+            std::cmp::min(width, child_max_size.width),
+            child_min_size.width,
+        );
+        height = std::cmp::max(
+            std::cmp::min(height, child_max_size.height),
+            self.child_min_height(width, child_min_size, child_max_size),
+        );
 
         self.child.layout(x, y, width, height);
     }
 
+    fn child_min_height(&self, width: i32, child_min_size: Size, child_max_size: Size) -> i32 {
+        if !self.child.has_height_for_width() {
+            return child_min_size.height;
+        }
+        let width = std::cmp::max(
+            std::cmp::min(width, child_max_size.width),
+            child_min_size.width,
+        );
+        self.child.min_height_for_width(width)
+    }
+
     fn new(props: ScrollableProperties<G>, child: Rc<dyn LayoutElement>) -> Self {
+        props.notifier.adopt(&*child);
+        if props.auto_refresh {
+            props.notifier.enable_auto_refresh();
+        }
         Self { props, child }
     }
 }
@@ -97,6 +171,8 @@ impl<G: GroupExt + Clone> ScrollableBuilder<G> {
                 mode: ScrollMode::Vertical,
                 horz_gap: 0,
                 vert_gap: 0,
+                auto_refresh: false,
+                notifier: LayoutNotifier::new(),
             },
         }
     }
@@ -122,6 +198,12 @@ impl<G: GroupExt + Clone> ScrollableBuilder<G> {
         self
     }
 
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn add<E: LayoutElement + 'static>(self, element: E) -> Scrollable<G> {
         self.add_shared(Rc::new(element))
     }
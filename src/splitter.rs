@@ -0,0 +1,659 @@
+use std::borrow::Borrow;
+use std::cell::{Cell, Ref, RefCell};
+use std::rc::{Rc, Weak};
+
+use fltk::enums::{Cursor, Event, FrameType};
+use fltk::frame::Frame;
+use fltk::group::Group;
+use fltk::prelude::{GroupExt, WidgetBase, WidgetExt};
+use fltk::widget::Widget;
+
+use crate::{IntoWidget, LayoutElement, LayoutNotifier, Padding, Size, WrapperFactory};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitterDirection {
+    Horizontal,
+    Vertical,
+}
+
+pub struct Splitter<G: GroupExt + Clone = Group> {
+    inner: Rc<SplitterInner<G>>,
+}
+
+pub struct SplitterBuilder<G: GroupExt + Clone = Group, F: Borrow<WrapperFactory> = WrapperFactory>
+{
+    props: SplitterProperties<G>,
+    factory: F,
+}
+
+pub struct SplitterPaneBuilder<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> {
+    owner: &'l mut SplitterBuilder<G, F>,
+    stretch: u8,
+}
+
+struct SplitterInner<G: GroupExt + Clone> {
+    props: SplitterProperties<G>,
+    cache: RefCell<SplitterCache>,
+    pane_sizes: RefCell<Vec<i32>>,
+    sashes: Vec<Frame>,
+    drag: Cell<Option<SplitterDrag>>,
+}
+
+struct SplitterProperties<G: GroupExt + Clone> {
+    group: G,
+    direction: SplitterDirection,
+    padding: Padding,
+    sash_width: i32,
+    panes: Vec<SplitterPane>,
+    auto_refresh: bool,
+    notifier: Rc<LayoutNotifier>,
+}
+
+struct SplitterPane {
+    element: Rc<dyn LayoutElement>,
+    stretch: u8,
+}
+
+struct SplitterCache {
+    min_sizes: Vec<i32>,
+    max_sizes: Vec<i32>,
+    stretches: Vec<u8>,
+    min_size: Size,
+    max_size: Size,
+}
+
+#[derive(Clone, Copy)]
+struct SplitterDrag {
+    sash: usize,
+    origin: i32,
+    before_size: i32,
+    after_size: i32,
+}
+
+impl<G: GroupExt + Clone> LayoutElement for Splitter<G> {
+    fn min_size(&self) -> Size {
+        self.inner.cache().min_size
+    }
+
+    fn max_size(&self) -> Size {
+        self.inner.cache().max_size
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.inner.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        self.inner.invalidate();
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.inner.props.notifier))
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.inner.props.group.clone().resize(x, y, width, height);
+        self.layout_children();
+    }
+}
+
+impl Splitter {
+    pub fn builder(direction: SplitterDirection) -> SplitterBuilder<Group, WrapperFactory> {
+        SplitterBuilder::new(Group::default_fill(), direction)
+    }
+
+    pub fn builder_with_factory<F: Borrow<WrapperFactory>>(
+        direction: SplitterDirection,
+        factory: F,
+    ) -> SplitterBuilder<Group, F> {
+        SplitterBuilder::with_factory(Group::default_fill(), direction, factory)
+    }
+}
+
+impl<G: GroupExt + Clone> Splitter<G> {
+    pub fn group(&self) -> G {
+        self.inner.props.group.clone()
+    }
+
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.inner.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
+    pub fn layout_children(&self) {
+        if self.inner.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+        self.inner.layout_panes();
+    }
+
+    pub fn pane_sizes(&self) -> Vec<i32> {
+        self.inner.pane_sizes.borrow().clone()
+    }
+
+    pub fn set_pane_sizes(&self, sizes: &[i32]) {
+        {
+            let cache = self.inner.cache();
+            let mut pane_sizes = self.inner.pane_sizes.borrow_mut();
+            pane_sizes.clear();
+            pane_sizes.extend(
+                cache
+                    .min_sizes
+                    .iter()
+                    .zip(cache.max_sizes.iter())
+                    .enumerate()
+                    .map(|(idx, (&min_size, &max_size))| {
+                        clamp_size(
+                            sizes.get(idx).copied().unwrap_or_default(),
+                            min_size,
+                            max_size,
+                        )
+                    }),
+            );
+        }
+        self.inner.layout_panes();
+    }
+}
+
+impl<G: GroupExt + Clone> SplitterInner<G> {
+    fn invalidate(&self) {
+        for pane in self.props.panes.iter() {
+            pane.element.invalidate();
+        }
+        self.props.notifier.take_stale();
+        *self.cache.borrow_mut() = SplitterCache::new(&self.props);
+    }
+
+    fn cache(&self) -> Ref<'_, SplitterCache> {
+        if self.props.notifier.take_stale() {
+            *self.cache.borrow_mut() = SplitterCache::new(&self.props);
+        }
+        self.cache.borrow()
+    }
+
+    fn layout_panes(&self) {
+        let group = &self.props.group;
+        let padding = &self.props.padding;
+        let x = group.x() + padding.left;
+        let y = group.y() + padding.top;
+        let width = group.width() - (padding.left + padding.right);
+        let height = group.height() - (padding.top + padding.bottom);
+
+        let cache = self.cache();
+        let mut pane_sizes = self.pane_sizes.borrow_mut();
+
+        let num_panes = self.props.panes.len();
+        let main_size = match self.props.direction {
+            SplitterDirection::Horizontal => width,
+            SplitterDirection::Vertical => height,
+        };
+        let available = main_size - self.total_sash_width();
+
+        if pane_sizes.len() != num_panes {
+            *pane_sizes = cache.min_sizes.clone();
+        }
+        for (size, (&min_size, &max_size)) in pane_sizes
+            .iter_mut()
+            .zip(cache.min_sizes.iter().zip(cache.max_sizes.iter()))
+        {
+            *size = clamp_size(*size, min_size, max_size);
+        }
+        let delta = available - pane_sizes.iter().sum::<i32>();
+        distribute_delta(
+            &mut pane_sizes,
+            &cache.min_sizes,
+            &cache.max_sizes,
+            &cache.stretches,
+            delta,
+        );
+
+        let mut pos = 0;
+        for (idx, (pane, &size)) in self.props.panes.iter().zip(pane_sizes.iter()).enumerate() {
+            match self.props.direction {
+                SplitterDirection::Horizontal => pane.element.layout(x + pos, y, size, height),
+                SplitterDirection::Vertical => pane.element.layout(x, y + pos, width, size),
+            }
+            pos += size;
+            if let Some(sash) = self.sashes.get(idx) {
+                let mut sash = sash.clone();
+                match self.props.direction {
+                    SplitterDirection::Horizontal => {
+                        sash.resize(x + pos, y, self.props.sash_width, height)
+                    }
+                    SplitterDirection::Vertical => {
+                        sash.resize(x, y + pos, width, self.props.sash_width)
+                    }
+                }
+            }
+            pos += self.props.sash_width;
+        }
+    }
+
+    fn total_sash_width(&self) -> i32 {
+        std::cmp::max(0, self.props.panes.len() as i32 - 1) * self.props.sash_width
+    }
+
+    fn main_pos(&self, x: i32, y: i32) -> i32 {
+        match self.props.direction {
+            SplitterDirection::Horizontal => x - (self.props.group.x() + self.props.padding.left),
+            SplitterDirection::Vertical => y - (self.props.group.y() + self.props.padding.top),
+        }
+    }
+
+    fn handle_sash_event(&self, sash: usize, event: Event) -> bool {
+        let pos = self.main_pos(fltk::app::event_x(), fltk::app::event_y());
+        match event {
+            Event::Push => {
+                let pane_sizes = self.pane_sizes.borrow();
+                if sash + 1 >= pane_sizes.len() {
+                    return false;
+                }
+                self.drag.set(Some(SplitterDrag {
+                    sash,
+                    origin: pos,
+                    before_size: pane_sizes[sash],
+                    after_size: pane_sizes[sash + 1],
+                }));
+                true
+            }
+            Event::Drag => {
+                let drag = match self.drag.get() {
+                    Some(drag) => drag,
+                    None => return false,
+                };
+                self.drag_sash(drag, pos - drag.origin);
+                true
+            }
+            Event::Released => self.drag.take().is_some(),
+            Event::Enter | Event::Move => {
+                self.set_cursor(match self.props.direction {
+                    SplitterDirection::Horizontal => Cursor::WE,
+                    SplitterDirection::Vertical => Cursor::NS,
+                });
+                true
+            }
+            Event::Leave => {
+                self.set_cursor(Cursor::Default);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn drag_sash(&self, drag: SplitterDrag, offset: i32) {
+        {
+            let cache = self.cache();
+            let offset = clamp_drag_offset(
+                offset,
+                (drag.before_size, drag.after_size),
+                (cache.min_sizes[drag.sash], cache.max_sizes[drag.sash]),
+                (
+                    cache.min_sizes[drag.sash + 1],
+                    cache.max_sizes[drag.sash + 1],
+                ),
+            );
+
+            let mut pane_sizes = self.pane_sizes.borrow_mut();
+            pane_sizes[drag.sash] = drag.before_size + offset;
+            pane_sizes[drag.sash + 1] = drag.after_size - offset;
+        }
+        self.layout_panes();
+        self.props.group.clone().redraw();
+    }
+
+    fn set_cursor(&self, cursor: Cursor) {
+        if let Some(mut window) = self.props.group.window() {
+            window.set_cursor(cursor);
+        }
+    }
+}
+
+impl SplitterCache {
+    fn new<G: GroupExt + Clone>(props: &SplitterProperties<G>) -> Self {
+        let mut min_sizes = Vec::with_capacity(props.panes.len());
+        let mut max_sizes = Vec::with_capacity(props.panes.len());
+        let mut stretches = Vec::with_capacity(props.panes.len());
+        let mut main_size = 0;
+        let mut cross_size = 0;
+        let mut main_max = 0i32;
+        let mut cross_max = i32::MAX;
+        for pane in props.panes.iter() {
+            let min_size = pane.element.min_size();
+            let max_size = pane.element.max_size();
+            let (pane_main, pane_cross, pane_main_max, pane_cross_max) = match props.direction {
+                SplitterDirection::Horizontal => (
+                    min_size.width,
+                    min_size.height,
+                    max_size.width,
+                    max_size.height,
+                ),
+                SplitterDirection::Vertical => (
+                    min_size.height,
+                    min_size.width,
+                    max_size.height,
+                    max_size.width,
+                ),
+            };
+            let pane_main_max = std::cmp::max(pane_main_max, pane_main);
+            min_sizes.push(pane_main);
+            max_sizes.push(pane_main_max);
+            stretches.push(pane.stretch);
+            main_size += pane_main;
+            cross_size = std::cmp::max(cross_size, pane_cross);
+            main_max =
+                main_max.saturating_add(if pane.stretch > 0 { pane_main_max } else { pane_main });
+            cross_max = std::cmp::min(cross_max, pane_cross_max);
+        }
+        let sashes = std::cmp::max(0, props.panes.len() as i32 - 1) * props.sash_width;
+        main_size += sashes;
+        main_max = main_max.saturating_add(sashes);
+        let cross_max = std::cmp::max(cross_max, cross_size);
+
+        let (width, height) = match props.direction {
+            SplitterDirection::Horizontal => (main_size, cross_size),
+            SplitterDirection::Vertical => (cross_size, main_size),
+        };
+        let (max_width, max_height) = match props.direction {
+            SplitterDirection::Horizontal => (main_max, cross_max),
+            SplitterDirection::Vertical => (cross_max, main_max),
+        };
+        let padding_width = props.padding.left + props.padding.right;
+        let padding_height = props.padding.top + props.padding.bottom;
+        Self {
+            min_sizes,
+            max_sizes,
+            stretches,
+            min_size: Size {
+                width: width + padding_width,
+                height: height + padding_height,
+            },
+            max_size: Size {
+                width: max_width.saturating_add(padding_width),
+                height: max_height.saturating_add(padding_height),
+            },
+        }
+    }
+}
+
+impl<G: GroupExt + Clone> SplitterBuilder<G> {
+    pub fn new(group: G, direction: SplitterDirection) -> Self {
+        Self::with_factory(group, direction, WrapperFactory::with_defaults())
+    }
+}
+
+impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> SplitterBuilder<G, F> {
+    pub fn with_factory(group: G, direction: SplitterDirection, factory: F) -> Self {
+        Self {
+            props: SplitterProperties {
+                group,
+                direction,
+                padding: Default::default(),
+                sash_width: 5,
+                panes: Vec::new(),
+                auto_refresh: false,
+                notifier: LayoutNotifier::new(),
+            },
+            factory,
+        }
+    }
+
+    pub fn with_sash_width(mut self, width: i32) -> Self {
+        self.props.sash_width = std::cmp::max(1, width);
+        self
+    }
+
+    pub fn with_left_padding(mut self, padding: i32) -> Self {
+        self.props.padding.left = padding;
+        self
+    }
+
+    pub fn with_top_padding(mut self, padding: i32) -> Self {
+        self.props.padding.top = padding;
+        self
+    }
+
+    pub fn with_right_padding(mut self, padding: i32) -> Self {
+        self.props.padding.right = padding;
+        self
+    }
+
+    pub fn with_bottom_padding(mut self, padding: i32) -> Self {
+        self.props.padding.bottom = padding;
+        self
+    }
+
+    pub fn with_padding(mut self, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        self.props.padding = Padding {
+            left,
+            top,
+            right,
+            bottom,
+        };
+        self
+    }
+
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
+    pub fn pane(&mut self) -> SplitterPaneBuilder<'_, G, F> {
+        SplitterPaneBuilder {
+            owner: self,
+            stretch: 1,
+        }
+    }
+
+    pub fn add<E: LayoutElement + 'static>(&mut self, element: E) {
+        self.pane().add(element);
+    }
+
+    pub fn add_shared(&mut self, element: Rc<dyn LayoutElement>) {
+        self.pane().add_shared(element);
+    }
+
+    pub fn wrap<W: IntoWidget + 'static>(&mut self, widget: W) -> W {
+        self.pane().wrap(widget)
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.pane().wrap_with(widget, wrapper)
+    }
+}
+
+impl<G: GroupExt + WidgetBase + Clone + 'static, F: Borrow<WrapperFactory>> SplitterBuilder<G, F> {
+    pub fn end(mut self) -> Splitter<G> {
+        self.props.group.end();
+        if self.props.auto_refresh {
+            self.props.notifier.enable_auto_refresh();
+        }
+
+        let num_sashes = self.props.panes.len().saturating_sub(1);
+        let sashes: Vec<Frame> = (0..num_sashes)
+            .map(|_| {
+                let mut sash = Frame::default();
+                sash.set_frame(FrameType::ThinUpBox);
+                self.props.group.add(&sash);
+                sash
+            })
+            .collect();
+
+        let cache = RefCell::new(SplitterCache::new(&self.props));
+        let inner = Rc::new(SplitterInner {
+            props: self.props,
+            cache,
+            pane_sizes: RefCell::new(Vec::new()),
+            sashes,
+            drag: Cell::new(None),
+        });
+
+        for (idx, sash) in inner.sashes.iter().enumerate() {
+            let weak_inner: Weak<SplitterInner<G>> = Rc::downgrade(&inner);
+            sash.clone().handle(move |_, event| {
+                weak_inner
+                    .upgrade()
+                    .is_some_and(|inner| inner.handle_sash_event(idx, event))
+            });
+        }
+
+        Splitter { inner }
+    }
+}
+
+impl<'l, G: GroupExt + Clone, F: Borrow<WrapperFactory>> SplitterPaneBuilder<'l, G, F> {
+    pub fn with_stretch(mut self, stretch: u8) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
+    pub fn add<E: LayoutElement + 'static>(self, element: E) {
+        self.add_shared(Rc::new(element));
+    }
+
+    pub fn add_shared(self, element: Rc<dyn LayoutElement>) {
+        self.owner.props.notifier.adopt(&*element);
+        self.owner.props.panes.push(SplitterPane {
+            element,
+            stretch: self.stretch,
+        });
+    }
+
+    pub fn wrap<W: IntoWidget + 'static>(self, widget: W) -> W {
+        let element = self.owner.factory.borrow().wrap(widget.clone());
+        self.add_shared(element);
+        widget
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.add_shared(Rc::new(wrapper(widget.clone())));
+        widget
+    }
+}
+
+fn distribute_delta(
+    sizes: &mut [i32],
+    min_sizes: &[i32],
+    max_sizes: &[i32],
+    stretches: &[u8],
+    mut delta: i32,
+) {
+    while delta != 0 {
+        let candidates: Vec<usize> = (0..sizes.len())
+            .filter(|&idx| {
+                (stretches[idx] > 0)
+                    && (((delta > 0) && (sizes[idx] < max_sizes[idx]))
+                        || ((delta < 0) && (sizes[idx] > min_sizes[idx])))
+            })
+            .collect();
+        if candidates.is_empty() {
+            break;
+        }
+
+        let total_stretch: i32 = candidates.iter().map(|&idx| stretches[idx] as i32).sum();
+        let mut remaining = delta;
+        for (pos, &idx) in candidates.iter().enumerate() {
+            let share = if pos + 1 == candidates.len() {
+                remaining
+            } else {
+                delta * stretches[idx] as i32 / total_stretch
+            };
+            let new_size = clamp_size(sizes[idx] + share, min_sizes[idx], max_sizes[idx]);
+            remaining -= new_size - sizes[idx];
+            sizes[idx] = new_size;
+        }
+
+        if remaining == delta {
+            break;
+        }
+        delta = remaining;
+    }
+
+    if let Some(last) = sizes.len().checked_sub(1) {
+        sizes[last] = clamp_size(sizes[last] + delta, min_sizes[last], max_sizes[last]);
+    }
+}
+
+fn clamp_size(size: i32, min_size: i32, max_size: i32) -> i32 {
+    std::cmp::max(std::cmp::min(size, max_size), min_size)
+}
+
+fn clamp_drag_offset(
+    offset: i32,
+    (before_size, after_size): (i32, i32),
+    (min_before, max_before): (i32, i32),
+    (min_after, max_after): (i32, i32),
+) -> i32 {
+    offset
+        .max(min_before - before_size)
+        .max(after_size.saturating_sub(max_after))
+        .min(max_before.saturating_sub(before_size))
+        .min(after_size - min_after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta_follows_stretch() {
+        let mut sizes = vec![10, 10, 10];
+        distribute_delta(&mut sizes, &[10; 3], &[i32::MAX; 3], &[1, 0, 3], 40);
+        assert_eq!(sizes, vec![20, 10, 40]);
+    }
+
+    #[test]
+    fn delta_clamps_at_max() {
+        let mut sizes = vec![10, 10];
+        distribute_delta(&mut sizes, &[10, 10], &[15, i32::MAX], &[1, 1], 40);
+        assert_eq!(sizes, vec![15, 45]);
+
+        let mut sizes = vec![10, 10];
+        distribute_delta(&mut sizes, &[10, 10], &[15, 20], &[1, 1], 40);
+        assert_eq!(sizes, vec![15, 20]);
+    }
+
+    #[test]
+    fn delta_clamps_at_min() {
+        let mut sizes = vec![30, 30];
+        distribute_delta(&mut sizes, &[25, 10], &[i32::MAX; 2], &[1, 1], -20);
+        assert_eq!(sizes, vec![25, 15]);
+
+        let mut sizes = vec![30, 30];
+        distribute_delta(&mut sizes, &[25, 20], &[i32::MAX; 2], &[1, 1], -30);
+        assert_eq!(sizes, vec![25, 20]);
+    }
+
+    #[test]
+    fn drag_clamps_at_min() {
+        let limits = (10, 100);
+        assert_eq!(clamp_drag_offset(-50, (30, 30), limits, limits), -20);
+        assert_eq!(clamp_drag_offset(50, (30, 30), limits, limits), 20);
+        assert_eq!(clamp_drag_offset(5, (30, 30), limits, limits), 5);
+    }
+
+    #[test]
+    fn drag_clamps_at_max() {
+        assert_eq!(
+            clamp_drag_offset(50, (30, 80), (10, 40), (10, i32::MAX)),
+            10
+        );
+        assert_eq!(
+            clamp_drag_offset(-50, (80, 30), (10, i32::MAX), (10, 40)),
+            -10
+        );
+        assert_eq!(
+            clamp_drag_offset(50, (30, 80), (10, i32::MAX), (10, i32::MAX)),
+            50
+        );
+    }
+}
@@ -0,0 +1,463 @@
+use std::borrow::Borrow;
+use std::cell::{Cell, Ref, RefCell};
+use std::rc::Rc;
+
+use fltk::group::Group;
+use fltk::prelude::{GroupExt, WidgetBase, WidgetExt};
+use fltk::widget::Widget;
+
+use crate::{IntoWidget, LayoutElement, LayoutNotifier, Padding, Size, WrapperFactory};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackSizing {
+    AllPages,
+    CurrentPage,
+}
+
+pub struct Stack<G: GroupExt + Clone = Group> {
+    props: StackProperties<G>,
+    cache: RefCell<StackCache>,
+    current: Cell<usize>,
+}
+
+pub struct StackBuilder<G: GroupExt + Clone = Group, F: Borrow<WrapperFactory> = WrapperFactory> {
+    props: StackProperties<G>,
+    factory: F,
+}
+
+struct StackProperties<G: GroupExt + Clone> {
+    group: G,
+    padding: Padding,
+    sizing: StackSizing,
+    pages: Vec<Rc<dyn LayoutElement>>,
+    auto_refresh: bool,
+    notifier: Rc<LayoutNotifier>,
+}
+
+struct StackCache {
+    min_size: Size,
+    max_size: Size,
+    height_for_width: bool,
+}
+
+impl<G: GroupExt + Clone> LayoutElement for Stack<G> {
+    fn min_size(&self) -> Size {
+        self.cache().min_size
+    }
+
+    fn max_size(&self) -> Size {
+        self.cache().max_size
+    }
+
+    fn has_height_for_width(&self) -> bool {
+        self.cache().height_for_width
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        if !self.has_height_for_width() {
+            return self.min_size().height;
+        }
+
+        let width = width - (self.props.padding.left + self.props.padding.right);
+        let height = sized_pages(&self.props.pages, self.props.sizing, self.current.get())
+            .map(|page| {
+                let page_width = std::cmp::min(width, page.max_size().width);
+                page.min_height_for_width(page_width)
+            })
+            .max()
+            .unwrap_or_default();
+        height + self.props.padding.top + self.props.padding.bottom
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        for page in self.props.pages.iter() {
+            page.invalidate();
+        }
+        self.props.notifier.take_stale();
+        *self.cache.borrow_mut() = StackCache::new(&self.props, self.current.get());
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.props.notifier))
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.props.group.clone().resize(x, y, width, height);
+        self.layout_children();
+    }
+}
+
+impl Stack {
+    pub fn builder() -> StackBuilder<Group, WrapperFactory> {
+        StackBuilder::new(Group::default_fill())
+    }
+
+    pub fn builder_with_factory<F: Borrow<WrapperFactory>>(factory: F) -> StackBuilder<Group, F> {
+        StackBuilder::with_factory(Group::default_fill(), factory)
+    }
+}
+
+impl<G: GroupExt + Clone> Stack<G> {
+    pub fn group(&self) -> G {
+        self.props.group.clone()
+    }
+
+    pub fn num_pages(&self) -> usize {
+        self.props.pages.len()
+    }
+
+    pub fn current(&self) -> usize {
+        self.current.get()
+    }
+
+    pub fn set_current(&self, page: usize) {
+        if page >= self.props.pages.len() {
+            return;
+        }
+
+        self.current.set(page);
+        self.update_visibility();
+        if self.props.sizing == StackSizing::CurrentPage {
+            *self.cache.borrow_mut() = StackCache::new(&self.props, page);
+            if !self.props.notifier.notify_parent() {
+                self.layout_children();
+            }
+        } else {
+            self.layout_children();
+        }
+        self.props.group.clone().redraw();
+    }
+
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
+    pub fn layout_children(&self) {
+        if self.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+
+        let x = self.props.group.x() + self.props.padding.left;
+        let y = self.props.group.y() + self.props.padding.top;
+        let width = self.props.group.width() - (self.props.padding.left + self.props.padding.right);
+        let height =
+            self.props.group.height() - (self.props.padding.top + self.props.padding.bottom);
+
+        for page in self.props.pages.iter() {
+            let max_size = page.max_size();
+            let page_width = std::cmp::min(width, max_size.width);
+            let page_height = std::cmp::min(height, max_size.height);
+            page.layout(x, y, page_width, page_height);
+        }
+    }
+
+    fn new(props: StackProperties<G>) -> Self {
+        if props.auto_refresh {
+            props.notifier.enable_auto_refresh();
+        }
+        let cache = RefCell::new(StackCache::new(&props, 0));
+        let stack = Self {
+            props,
+            cache,
+            current: Cell::new(0),
+        };
+        stack.update_visibility();
+        stack
+    }
+
+    fn cache(&self) -> Ref<'_, StackCache> {
+        if self.props.notifier.take_stale() {
+            *self.cache.borrow_mut() = StackCache::new(&self.props, self.current.get());
+        }
+        self.cache.borrow()
+    }
+
+    fn update_visibility(&self) {
+        for (page, visible) in page_visibility(&self.props.pages, self.current.get()) {
+            if let Some(mut widget) = page.widget() {
+                if visible {
+                    widget.show();
+                } else {
+                    widget.hide();
+                }
+            }
+        }
+    }
+}
+
+impl StackCache {
+    fn new<G: GroupExt + Clone>(props: &StackProperties<G>, current: usize) -> Self {
+        Self::for_pages(&props.pages, props.sizing, &props.padding, current)
+    }
+
+    fn for_pages(
+        pages: &[Rc<dyn LayoutElement>],
+        sizing: StackSizing,
+        padding: &Padding,
+        current: usize,
+    ) -> Self {
+        let mut min_size = sized_pages(pages, sizing, current)
+            .map(|page| page.min_size())
+            .fold(Default::default(), |lhs: Size, rhs: Size| Size {
+                width: std::cmp::max(lhs.width, rhs.width),
+                height: std::cmp::max(lhs.height, rhs.height),
+            });
+        min_size.width += padding.left + padding.right;
+        min_size.height += padding.top + padding.bottom;
+
+        let mut max_size = sized_pages(pages, sizing, current)
+            .map(|page| page.max_size())
+            .fold(Default::default(), |lhs: Size, rhs: Size| Size {
+                width: std::cmp::max(lhs.width, rhs.width),
+                height: std::cmp::max(lhs.height, rhs.height),
+            });
+        max_size.width = max_size.width.saturating_add(padding.left + padding.right);
+        max_size.height = max_size.height.saturating_add(padding.top + padding.bottom);
+        max_size.width = std::cmp::max(max_size.width, min_size.width);
+        max_size.height = std::cmp::max(max_size.height, min_size.height);
+
+        let height_for_width =
+            sized_pages(pages, sizing, current).any(|page| page.has_height_for_width());
+
+        Self {
+            min_size,
+            max_size,
+            height_for_width,
+        }
+    }
+}
+
+impl<G: GroupExt + Clone> StackBuilder<G> {
+    pub fn new(group: G) -> Self {
+        Self::with_factory(group, WrapperFactory::with_defaults())
+    }
+}
+
+impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> StackBuilder<G, F> {
+    pub fn with_factory(group: G, factory: F) -> Self {
+        Self {
+            props: StackProperties {
+                group,
+                padding: Default::default(),
+                sizing: StackSizing::AllPages,
+                pages: Vec::new(),
+                auto_refresh: false,
+                notifier: LayoutNotifier::new(),
+            },
+            factory,
+        }
+    }
+
+    pub fn with_left_padding(mut self, padding: i32) -> Self {
+        self.props.padding.left = padding;
+        self
+    }
+
+    pub fn with_top_padding(mut self, padding: i32) -> Self {
+        self.props.padding.top = padding;
+        self
+    }
+
+    pub fn with_right_padding(mut self, padding: i32) -> Self {
+        self.props.padding.right = padding;
+        self
+    }
+
+    pub fn with_bottom_padding(mut self, padding: i32) -> Self {
+        self.props.padding.bottom = padding;
+        self
+    }
+
+    pub fn with_padding(mut self, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        self.props.padding = Padding {
+            left,
+            top,
+            right,
+            bottom,
+        };
+        self
+    }
+
+    pub fn with_sizing(mut self, sizing: StackSizing) -> Self {
+        self.props.sizing = sizing;
+        self
+    }
+
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
+    pub fn add<E: LayoutElement + 'static>(&mut self, element: E) {
+        self.add_shared(Rc::new(element));
+    }
+
+    pub fn add_shared(&mut self, element: Rc<dyn LayoutElement>) {
+        if let Some(widget) = element.widget() {
+            if widget.parent().is_none() {
+                self.props.group.add(&widget);
+            }
+        }
+        self.props.notifier.adopt(&*element);
+        self.props.pages.push(element);
+    }
+
+    pub fn wrap<W: IntoWidget + 'static>(&mut self, widget: W) -> W {
+        let element = self.factory.borrow().wrap(widget.clone());
+        self.add_widget(widget, element)
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        let element = Rc::new(wrapper(widget.clone()));
+        self.add_widget(widget, element)
+    }
+
+    fn add_widget<W: IntoWidget + 'static>(
+        &mut self,
+        widget: W,
+        element: Rc<dyn LayoutElement>,
+    ) -> W {
+        let base = widget.clone().into_widget();
+        let in_group = base
+            .parent()
+            .map(|parent| parent.as_widget_ptr() == self.props.group.as_widget_ptr())
+            .unwrap_or_default();
+        if !in_group {
+            self.props.group.add(&base);
+        }
+        self.add_shared(element);
+        widget
+    }
+
+    pub fn end(self) -> Stack<G> {
+        self.props.group.end();
+        Stack::new(self.props)
+    }
+}
+
+fn sized_pages(
+    pages: &[Rc<dyn LayoutElement>],
+    sizing: StackSizing,
+    current: usize,
+) -> impl Iterator<Item = &Rc<dyn LayoutElement>> {
+    pages
+        .iter()
+        .enumerate()
+        .filter(move |(idx, _)| (sizing == StackSizing::AllPages) || (*idx == current))
+        .map(|(_, page)| page)
+}
+
+fn page_visibility(
+    pages: &[Rc<dyn LayoutElement>],
+    current: usize,
+) -> impl Iterator<Item = (&Rc<dyn LayoutElement>, bool)> {
+    pages
+        .iter()
+        .enumerate()
+        .map(move |(idx, page)| (page, idx == current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakePage {
+        min_size: Size,
+    }
+
+    impl LayoutElement for FakePage {
+        fn min_size(&self) -> Size {
+            self.min_size
+        }
+
+        fn layout(&self, _x: i32, _y: i32, _width: i32, _height: i32) {}
+
+        fn is_visible(&self) -> bool {
+            true
+        }
+    }
+
+    fn pages() -> Vec<Rc<dyn LayoutElement>> {
+        [(40, 10), (20, 30), (10, 5)]
+            .iter()
+            .map(|&(width, height)| {
+                Rc::new(FakePage {
+                    min_size: Size { width, height },
+                }) as Rc<dyn LayoutElement>
+            })
+            .collect()
+    }
+
+    fn visible_pages(pages: &[Rc<dyn LayoutElement>], current: usize) -> Vec<bool> {
+        page_visibility(pages, current)
+            .map(|(_, visible)| visible)
+            .collect()
+    }
+
+    #[test]
+    fn current_page_is_the_only_visible_one() {
+        let pages = pages();
+        assert_eq!(visible_pages(&pages, 0), vec![true, false, false]);
+        assert_eq!(visible_pages(&pages, 2), vec![false, false, true]);
+    }
+
+    #[test]
+    fn all_pages_sizing_ignores_current() {
+        let pages = pages();
+        let padding = Padding {
+            left: 1,
+            top: 2,
+            right: 3,
+            bottom: 4,
+        };
+        for current in 0..pages.len() {
+            let cache = StackCache::for_pages(&pages, StackSizing::AllPages, &padding, current);
+            assert_eq!(
+                cache.min_size,
+                Size {
+                    width: 44,
+                    height: 36
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn current_page_sizing_follows_current() {
+        let pages = pages();
+        let padding = Default::default();
+        let min_sizes: Vec<Size> = (0..pages.len())
+            .map(|current| {
+                StackCache::for_pages(&pages, StackSizing::CurrentPage, &padding, current).min_size
+            })
+            .collect();
+        assert_eq!(
+            min_sizes,
+            vec![
+                Size {
+                    width: 40,
+                    height: 10
+                },
+                Size {
+                    width: 20,
+                    height: 30
+                },
+                Size {
+                    width: 10,
+                    height: 5
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::ops::{Deref, DerefMut};
+
+use fltk::enums::Font;
+use fltk::prelude::*;
+use fltk::widget::Widget;
+
+use super::browser::{char_width, scrollbar_size, DEFAULT_VISIBLE_CHARS, DEFAULT_VISIBLE_ROWS};
+use super::{LayoutElement, LayoutWidgetWrapper, Size};
+
+pub struct TableElement<T: TableExt + Clone> {
+    widget: T,
+    visible_rows: i32,
+    visible_chars: i32,
+}
+
+impl<T: TableExt + Clone> LayoutWidgetWrapper<T> for TableElement<T> {
+    fn wrap(widget: T) -> Self {
+        Self {
+            widget,
+            visible_rows: DEFAULT_VISIBLE_ROWS,
+            visible_chars: DEFAULT_VISIBLE_CHARS,
+        }
+    }
+}
+
+impl<T: TableExt + Clone> TableElement<T> {
+    pub fn with_visible_rows(mut self, rows: i32) -> Self {
+        self.visible_rows = std::cmp::max(1, rows);
+        self
+    }
+
+    pub fn with_visible_chars(mut self, chars: i32) -> Self {
+        self.visible_chars = std::cmp::max(1, chars);
+        self
+    }
+}
+
+impl<T: TableExt + Clone> LayoutElement for TableElement<T> {
+    fn min_size(&self) -> Size {
+        // Table cells are drawn by the application, so measure with FLTK's default font.
+        fltk::draw::set_font(Font::Helvetica, fltk::app::font_size());
+        let rows = std::cmp::min(self.widget.rows(), self.visible_rows);
+        let rows_height = (0..rows)
+            .map(|row| self.widget.row_height(row))
+            .sum::<i32>();
+        let line_height =
+            if rows > 0 { self.widget.row_height(rows - 1) } else { fltk::draw::height() };
+        let header_width =
+            if self.widget.row_header() { self.widget.row_header_width() } else { 0 };
+        let header_height =
+            if self.widget.col_header() { self.widget.col_header_height() } else { 0 };
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        Size {
+            width: self.visible_chars * char_width()
+                + header_width
+                + frame_width
+                + scrollbar_size(self.widget.scrollbar_size()),
+            height: rows_height
+                + (self.visible_rows - rows) * line_height
+                + header_height
+                + frame_height,
+        }
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl<T: TableExt + Clone> Deref for TableElement<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<T: TableExt + Clone> DerefMut for TableElement<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
@@ -0,0 +1,244 @@
+use std::borrow::Borrow;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use fltk::group::Tabs;
+use fltk::prelude::{GroupExt, WidgetBase, WidgetExt};
+use fltk::widget::Widget;
+
+use crate::{IntoWidget, LayoutElement, LayoutNotifier, Padding, Size, WrapperFactory};
+
+const TAB_EXTRA_SPACE: i32 = 10;
+const TAB_EXTRA_GAP: i32 = 2;
+
+pub struct TabsElement<G: GroupExt + Clone = Tabs> {
+    props: TabsProperties<G>,
+    cache: RefCell<TabsCache>,
+}
+
+pub struct TabsBuilder<G: GroupExt + Clone = Tabs, F: Borrow<WrapperFactory> = WrapperFactory> {
+    props: TabsProperties<G>,
+    factory: F,
+}
+
+struct TabsProperties<G: GroupExt + Clone> {
+    group: G,
+    padding: Padding,
+    pages: Vec<Rc<dyn LayoutElement>>,
+    auto_refresh: bool,
+    notifier: Rc<LayoutNotifier>,
+}
+
+struct TabsCache {
+    header_height: i32,
+    min_size: Size,
+}
+
+impl<G: GroupExt + Clone> LayoutElement for TabsElement<G> {
+    fn min_size(&self) -> Size {
+        self.cache().min_size
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.props.group.as_base_widget())
+    }
+
+    fn invalidate(&self) {
+        for page in self.props.pages.iter() {
+            page.invalidate();
+        }
+        self.props.notifier.take_stale();
+        *self.cache.borrow_mut() = TabsCache::new(&self.props);
+    }
+
+    fn notifier(&self) -> Option<Rc<LayoutNotifier>> {
+        Some(Rc::clone(&self.props.notifier))
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.props.group.clone().resize(x, y, width, height);
+        self.layout_children();
+    }
+}
+
+impl TabsElement {
+    pub fn builder() -> TabsBuilder<Tabs, WrapperFactory> {
+        TabsBuilder::new(Tabs::default_fill())
+    }
+
+    pub fn builder_with_factory<F: Borrow<WrapperFactory>>(factory: F) -> TabsBuilder<Tabs, F> {
+        TabsBuilder::with_factory(Tabs::default_fill(), factory)
+    }
+}
+
+impl<G: GroupExt + Clone> TabsElement<G> {
+    pub fn group(&self) -> G {
+        self.props.group.clone()
+    }
+
+    pub fn header_height(&self) -> i32 {
+        self.cache().header_height
+    }
+
+    pub fn refresh(&self) {
+        self.invalidate();
+        if !self.props.notifier.notify_parent() {
+            self.layout_children();
+        }
+    }
+
+    pub fn layout_children(&self) {
+        if self.props.notifier.should_auto_refresh() {
+            self.invalidate();
+        }
+
+        let header_height = self.cache().header_height;
+        let x = self.props.group.x() + self.props.padding.left;
+        let y = self.props.group.y() + header_height + self.props.padding.top;
+        let width = self.props.group.width() - (self.props.padding.left + self.props.padding.right);
+        let height = self.props.group.height()
+            - (header_height + self.props.padding.top + self.props.padding.bottom);
+
+        for page in self.props.pages.iter() {
+            page.layout(x, y, width, height);
+        }
+    }
+
+    fn new(props: TabsProperties<G>) -> Self {
+        if props.auto_refresh {
+            props.notifier.enable_auto_refresh();
+        }
+        let cache = RefCell::new(TabsCache::new(&props));
+        Self { props, cache }
+    }
+
+    fn cache(&self) -> Ref<'_, TabsCache> {
+        if self.props.notifier.take_stale() {
+            *self.cache.borrow_mut() = TabsCache::new(&self.props);
+        }
+        self.cache.borrow()
+    }
+}
+
+impl TabsCache {
+    fn new<G: GroupExt + Clone>(props: &TabsProperties<G>) -> Self {
+        let frame = props.group.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+
+        let mut tabs_width = TAB_EXTRA_GAP;
+        let mut label_height = 0;
+        for idx in 0..props.group.children() {
+            if let Some(tab) = props.group.child(idx) {
+                fltk::draw::set_font(tab.label_font(), tab.label_size());
+                let (tab_label_width, tab_label_height) = fltk::draw::measure(&tab.label(), true);
+                tabs_width += tab_label_width + TAB_EXTRA_SPACE + frame_width;
+                label_height = std::cmp::max(label_height, tab_label_height);
+            }
+        }
+        let header_height = label_height + 2 * frame_height;
+
+        let pages_size = props.pages.iter().map(|page| page.min_size()).fold(
+            Default::default(),
+            |lhs: Size, rhs: Size| Size {
+                width: std::cmp::max(lhs.width, rhs.width),
+                height: std::cmp::max(lhs.height, rhs.height),
+            },
+        );
+        let width = std::cmp::max(
+            tabs_width,
+            pages_size.width + props.padding.left + props.padding.right,
+        );
+        let height = header_height + pages_size.height + props.padding.top + props.padding.bottom;
+
+        Self {
+            header_height,
+            min_size: Size { width, height },
+        }
+    }
+}
+
+impl<G: GroupExt + Clone> TabsBuilder<G> {
+    pub fn new(group: G) -> Self {
+        Self::with_factory(group, WrapperFactory::with_defaults())
+    }
+}
+
+impl<G: GroupExt + Clone, F: Borrow<WrapperFactory>> TabsBuilder<G, F> {
+    pub fn with_factory(group: G, factory: F) -> Self {
+        Self {
+            props: TabsProperties {
+                group,
+                padding: Default::default(),
+                pages: Vec::new(),
+                auto_refresh: false,
+                notifier: LayoutNotifier::new(),
+            },
+            factory,
+        }
+    }
+
+    pub fn with_left_padding(mut self, padding: i32) -> Self {
+        self.props.padding.left = padding;
+        self
+    }
+
+    pub fn with_top_padding(mut self, padding: i32) -> Self {
+        self.props.padding.top = padding;
+        self
+    }
+
+    pub fn with_right_padding(mut self, padding: i32) -> Self {
+        self.props.padding.right = padding;
+        self
+    }
+
+    pub fn with_bottom_padding(mut self, padding: i32) -> Self {
+        self.props.padding.bottom = padding;
+        self
+    }
+
+    pub fn with_padding(mut self, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        self.props.padding = Padding {
+            left,
+            top,
+            right,
+            bottom,
+        };
+        self
+    }
+
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.props.auto_refresh = auto_refresh;
+        self
+    }
+
+    pub fn add<E: LayoutElement + 'static>(&mut self, element: E) {
+        self.add_shared(Rc::new(element));
+    }
+
+    pub fn add_shared(&mut self, element: Rc<dyn LayoutElement>) {
+        self.props.notifier.adopt(&*element);
+        self.props.pages.push(element);
+    }
+
+    pub fn wrap<W: IntoWidget + 'static>(&mut self, widget: W) -> W {
+        let element = self.factory.borrow().wrap(widget.clone());
+        self.add_shared(element);
+        widget
+    }
+
+    pub fn wrap_with<W: IntoWidget + 'static, L: LayoutElement + 'static>(
+        &mut self,
+        widget: W,
+        wrapper: impl FnOnce(W) -> L,
+    ) -> W {
+        self.add_shared(Rc::new(wrapper(widget.clone())));
+        widget
+    }
+
+    pub fn end(self) -> TabsElement<G> {
+        self.props.group.end();
+        TabsElement::new(self.props)
+    }
+}
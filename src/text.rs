@@ -1,16 +1,48 @@
 use std::ops::{Deref, DerefMut};
 
 use fltk::prelude::*;
+use fltk::text::WrapMode;
+use fltk::widget::Widget;
 
 use crate::{LayoutElement, LayoutWidgetWrapper, Size};
 
 pub struct TextElement<T: DisplayExt + Clone> {
     widget: T,
+    fit_wrapped_text: bool,
+    wrap_mode: WrapMode,
+    wrap_margin: i32,
 }
 
+const TEXT_MARGIN: i32 = 3;
+
 impl<T: DisplayExt + Clone> LayoutWidgetWrapper<T> for TextElement<T> {
     fn wrap(widget: T) -> Self {
-        Self { widget }
+        Self {
+            widget,
+            fit_wrapped_text: false,
+            wrap_mode: WrapMode::None,
+            wrap_margin: 0,
+        }
+    }
+}
+
+impl<T: DisplayExt + Clone> TextElement<T> {
+    pub fn with_fit_wrapped_text(mut self, fit: bool) -> Self {
+        self.fit_wrapped_text = fit;
+        self
+    }
+
+    // fltk-rs cannot query the wrap mode of a text display, so the element keeps track of the
+    // mode it sets on the widget.
+    pub fn with_wrap_mode(mut self, mode: WrapMode, margin: i32) -> Self {
+        self.widget.wrap_mode(mode, margin);
+        self.wrap_mode = mode;
+        self.wrap_margin = margin;
+        self
+    }
+
+    fn fits_wrapped_text(&self) -> bool {
+        self.fit_wrapped_text && self.wrap_mode != WrapMode::None
     }
 }
 
@@ -31,6 +63,50 @@ impl<T: DisplayExt + Clone> LayoutElement for TextElement<T> {
         }
     }
 
+    fn has_height_for_width(&self) -> bool {
+        self.fits_wrapped_text()
+    }
+
+    fn min_height_for_width(&self, width: i32) -> i32 {
+        if !self.fits_wrapped_text() {
+            return self.min_size().height;
+        }
+
+        let text = self
+            .widget
+            .buffer()
+            .map(|buffer| buffer.text())
+            .unwrap_or_default();
+        fltk::draw::set_font(self.widget.text_font(), self.widget.text_size());
+        let text_height = fltk::draw::height();
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        let text_width = width - frame_width - self.widget.linenumber_width() - 2 * TEXT_MARGIN;
+        let text_width = match self.wrap_mode {
+            WrapMode::AtPixel if self.wrap_margin > 0 => {
+                std::cmp::min(text_width, self.wrap_margin)
+            }
+            WrapMode::AtColumn if self.wrap_margin > 0 => {
+                let column_width = fltk::draw::width("0").ceil() as i32;
+                std::cmp::min(text_width, self.wrap_margin * column_width)
+            }
+            _ => text_width,
+        };
+        let (_, wrapped_height) =
+            fltk::draw::wrap_measure(&text, std::cmp::max(1, text_width), false);
+        std::cmp::max(text_height, wrapped_height) + frame_height + 1
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        fltk::draw::set_font(self.widget.text_font(), self.widget.text_size());
+        Some(self.widget.frame().dy() + 1 + fltk::draw::height() - fltk::draw::descent())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.widget.clone().resize(x, y, width, height);
     }
@@ -0,0 +1,77 @@
+use std::ops::{Deref, DerefMut};
+
+use fltk::prelude::*;
+use fltk::tree::Tree;
+use fltk::widget::Widget;
+
+use super::browser::{char_width, scrollbar_size, DEFAULT_VISIBLE_CHARS, DEFAULT_VISIBLE_ROWS};
+use super::{LayoutElement, LayoutWidgetWrapper, Size};
+
+pub struct TreeElement {
+    widget: Tree,
+    visible_rows: i32,
+    visible_chars: i32,
+}
+
+impl LayoutWidgetWrapper<Tree> for TreeElement {
+    fn wrap(widget: Tree) -> Self {
+        Self {
+            widget,
+            visible_rows: DEFAULT_VISIBLE_ROWS,
+            visible_chars: DEFAULT_VISIBLE_CHARS,
+        }
+    }
+}
+
+impl TreeElement {
+    pub fn with_visible_rows(mut self, rows: i32) -> Self {
+        self.visible_rows = std::cmp::max(1, rows);
+        self
+    }
+
+    pub fn with_visible_chars(mut self, chars: i32) -> Self {
+        self.visible_chars = std::cmp::max(1, chars);
+        self
+    }
+}
+
+impl LayoutElement for TreeElement {
+    fn min_size(&self) -> Size {
+        fltk::draw::set_font(self.widget.item_label_font(), self.widget.item_label_size());
+        let line_height = fltk::draw::height() + self.widget.line_spacing();
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        Size {
+            width: self.visible_chars * char_width()
+                + self.widget.margin_left()
+                + frame_width
+                + scrollbar_size(self.widget.scrollbar_size()),
+            height: self.visible_rows * line_height
+                + self.widget.margin_top()
+                + self.widget.margin_bottom()
+                + frame_height,
+        }
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl Deref for TreeElement {
+    type Target = Tree;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for TreeElement {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
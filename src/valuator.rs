@@ -0,0 +1,487 @@
+use std::ops::{Deref, DerefMut};
+
+use fltk::enums::Font;
+use fltk::misc::Spinner;
+use fltk::prelude::*;
+use fltk::valuator::{
+    Counter, CounterType, HorValueSlider, SimpleCounter, SliderType, ValueInput, ValueOutput,
+    ValueSlider,
+};
+use fltk::widget::Widget;
+
+use super::{LayoutElement, LayoutWidgetWrapper, Size};
+
+const MAX_VALUE_PRECISION: usize = 10;
+const COUNTER_NORMAL_TEXT_PERCENT: i32 = 40;
+const COUNTER_SIMPLE_TEXT_PERCENT: i32 = 60;
+
+pub trait ValueText {
+    fn value_text_font(&self) -> Font;
+    fn value_text_size(&self) -> i32;
+}
+
+pub struct SliderElement<S: ValuatorExt + Clone> {
+    widget: S,
+}
+
+impl<S: ValuatorExt + Clone> LayoutWidgetWrapper<S> for SliderElement<S> {
+    fn wrap(widget: S) -> Self {
+        Self { widget }
+    }
+}
+
+impl<S: ValuatorExt + Clone> LayoutElement for SliderElement<S> {
+    fn min_size(&self) -> Size {
+        let thickness = fltk::app::scrollbar_size();
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        if is_horizontal(&self.widget) {
+            Size {
+                width: 2 * thickness + frame_width,
+                height: thickness + frame_height,
+            }
+        } else {
+            Size {
+                width: thickness + frame_width,
+                height: 2 * thickness + frame_height,
+            }
+        }
+    }
+
+    fn max_size(&self) -> Size {
+        oriented_max_size(&self.widget, self.min_size())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl<S: ValuatorExt + Clone> Deref for SliderElement<S> {
+    type Target = S;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<S: ValuatorExt + Clone> DerefMut for SliderElement<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+pub struct ValueSliderElement<S: ValuatorExt + ValueText + Clone> {
+    widget: S,
+}
+
+impl<S: ValuatorExt + ValueText + Clone> LayoutWidgetWrapper<S> for ValueSliderElement<S> {
+    fn wrap(widget: S) -> Self {
+        Self { widget }
+    }
+}
+
+impl<S: ValuatorExt + ValueText + Clone> LayoutElement for ValueSliderElement<S> {
+    fn min_size(&self) -> Size {
+        let thickness = fltk::app::scrollbar_size();
+        let text = value_text_size(&self.widget);
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        if is_horizontal(&self.widget) {
+            Size {
+                width: text.width + 2 * thickness + frame_width,
+                height: std::cmp::max(thickness, text.height) + frame_height,
+            }
+        } else {
+            Size {
+                width: std::cmp::max(thickness, text.width) + frame_width,
+                height: text.height + 2 * thickness + frame_height,
+            }
+        }
+    }
+
+    fn max_size(&self) -> Size {
+        oriented_max_size(&self.widget, self.min_size())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl<S: ValuatorExt + ValueText + Clone> Deref for ValueSliderElement<S> {
+    type Target = S;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<S: ValuatorExt + ValueText + Clone> DerefMut for ValueSliderElement<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+pub struct ValueInputElement<V: ValuatorExt + ValueText + Clone> {
+    widget: V,
+}
+
+impl<V: ValuatorExt + ValueText + Clone> LayoutWidgetWrapper<V> for ValueInputElement<V> {
+    fn wrap(widget: V) -> Self {
+        Self { widget }
+    }
+}
+
+impl<V: ValuatorExt + ValueText + Clone> LayoutElement for ValueInputElement<V> {
+    fn min_size(&self) -> Size {
+        let text = value_text_size(&self.widget);
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        Size {
+            width: text.width + frame_width,
+            height: text.height + frame_height + 1,
+        }
+    }
+
+    fn max_size(&self) -> Size {
+        Size {
+            width: Size::UNBOUNDED.width,
+            height: self.min_size().height,
+        }
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        Some(value_text_baseline(&self.widget))
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl<V: ValuatorExt + ValueText + Clone> Deref for ValueInputElement<V> {
+    type Target = V;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<V: ValuatorExt + ValueText + Clone> DerefMut for ValueInputElement<V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+pub struct CounterElement<C: ValuatorExt + ValueText + Clone> {
+    widget: C,
+}
+
+impl<C: ValuatorExt + ValueText + Clone> LayoutWidgetWrapper<C> for CounterElement<C> {
+    fn wrap(widget: C) -> Self {
+        Self { widget }
+    }
+}
+
+impl<C: ValuatorExt + ValueText + Clone> LayoutElement for CounterElement<C> {
+    fn min_size(&self) -> Size {
+        let text = value_text_size(&self.widget);
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        let text_percent = match self.widget.get_type::<CounterType>() {
+            CounterType::Simple => COUNTER_SIMPLE_TEXT_PERCENT,
+            _ => COUNTER_NORMAL_TEXT_PERCENT,
+        };
+        let text_box_width = text.width + frame_width;
+        Size {
+            width: (text_box_width * 100 + text_percent - 1) / text_percent,
+            height: text.height + frame_height + 1,
+        }
+    }
+
+    fn max_size(&self) -> Size {
+        Size {
+            width: Size::UNBOUNDED.width,
+            height: self.min_size().height,
+        }
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        Some(value_text_baseline(&self.widget))
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl<C: ValuatorExt + ValueText + Clone> Deref for CounterElement<C> {
+    type Target = C;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<C: ValuatorExt + ValueText + Clone> DerefMut for CounterElement<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+pub struct SpinnerElement {
+    widget: Spinner,
+}
+
+impl LayoutWidgetWrapper<Spinner> for SpinnerElement {
+    fn wrap(widget: Spinner) -> Self {
+        Self { widget }
+    }
+}
+
+impl LayoutElement for SpinnerElement {
+    fn min_size(&self) -> Size {
+        fltk::draw::set_font(self.widget.text_font(), self.widget.text_size());
+        let text_height = fltk::draw::height();
+        let text_width = max_value_width(
+            self.widget.minimum(),
+            self.widget.maximum(),
+            self.widget.step(),
+        );
+
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        let height = text_height + frame_height + 1;
+        Size {
+            width: text_width + frame_width + height / 2 + 2,
+            height,
+        }
+    }
+
+    fn max_size(&self) -> Size {
+        Size {
+            width: Size::UNBOUNDED.width,
+            height: self.min_size().height,
+        }
+    }
+
+    fn baseline(&self) -> Option<i32> {
+        fltk::draw::set_font(self.widget.text_font(), self.widget.text_size());
+        Some(self.widget.frame().dy() + fltk::draw::height() - fltk::draw::descent())
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.widget.clone().resize(x, y, width, height);
+    }
+}
+
+impl Deref for SpinnerElement {
+    type Target = Spinner;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for SpinnerElement {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+pub struct DialElement<D: ValuatorExt + Clone> {
+    widget: D,
+}
+
+impl<D: ValuatorExt + Clone> LayoutWidgetWrapper<D> for DialElement<D> {
+    fn wrap(widget: D) -> Self {
+        Self { widget }
+    }
+}
+
+impl<D: ValuatorExt + Clone> LayoutElement for DialElement<D> {
+    fn min_size(&self) -> Size {
+        let diameter = 2 * fltk::app::scrollbar_size();
+        let frame = self.widget.frame();
+        Size {
+            width: diameter + frame.dx() + frame.dw(),
+            height: diameter + frame.dy() + frame.dh(),
+        }
+    }
+
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.as_base_widget())
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        let frame = self.widget.frame();
+        let frame_width = frame.dx() + frame.dw();
+        let frame_height = frame.dy() + frame.dh();
+        let diameter = std::cmp::max(0, std::cmp::min(width - frame_width, height - frame_height));
+        let dial_width = std::cmp::min(width, diameter + frame_width);
+        let dial_height = std::cmp::min(height, diameter + frame_height);
+        self.widget.clone().resize(
+            x + (width - dial_width) / 2,
+            y + (height - dial_height) / 2,
+            dial_width,
+            dial_height,
+        );
+    }
+}
+
+impl<D: ValuatorExt + Clone> Deref for DialElement<D> {
+    type Target = D;
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<D: ValuatorExt + Clone> DerefMut for DialElement<D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+impl ValueText for ValueSlider {
+    fn value_text_font(&self) -> Font {
+        self.text_font()
+    }
+
+    fn value_text_size(&self) -> i32 {
+        self.text_size()
+    }
+}
+
+impl ValueText for HorValueSlider {
+    fn value_text_font(&self) -> Font {
+        self.text_font()
+    }
+
+    fn value_text_size(&self) -> i32 {
+        self.text_size()
+    }
+}
+
+impl ValueText for ValueInput {
+    fn value_text_font(&self) -> Font {
+        self.text_font()
+    }
+
+    fn value_text_size(&self) -> i32 {
+        self.text_size()
+    }
+}
+
+impl ValueText for ValueOutput {
+    fn value_text_font(&self) -> Font {
+        self.text_font()
+    }
+
+    fn value_text_size(&self) -> i32 {
+        self.text_size()
+    }
+}
+
+// fltk-rs exposes no text font accessors for counters, so assume the defaults
+// Fl_Counter is constructed with: FL_HELVETICA at FL_NORMAL_SIZE.
+impl ValueText for Counter {
+    fn value_text_font(&self) -> Font {
+        Font::Helvetica
+    }
+
+    fn value_text_size(&self) -> i32 {
+        fltk::app::font_size()
+    }
+}
+
+impl ValueText for SimpleCounter {
+    fn value_text_font(&self) -> Font {
+        Font::Helvetica
+    }
+
+    fn value_text_size(&self) -> i32 {
+        fltk::app::font_size()
+    }
+}
+
+fn is_horizontal<W: WidgetExt>(widget: &W) -> bool {
+    (widget.get_type::<SliderType>() as i32) & 1 != 0
+}
+
+fn oriented_max_size<W: WidgetExt>(widget: &W, min_size: Size) -> Size {
+    if is_horizontal(widget) {
+        Size {
+            width: Size::UNBOUNDED.width,
+            height: min_size.height,
+        }
+    } else {
+        Size {
+            width: min_size.width,
+            height: Size::UNBOUNDED.height,
+        }
+    }
+}
+
+fn value_text_size<V: ValuatorExt + ValueText>(valuator: &V) -> Size {
+    fltk::draw::set_font(valuator.value_text_font(), valuator.value_text_size());
+    Size {
+        width: max_value_width(valuator.minimum(), valuator.maximum(), valuator.step()),
+        height: fltk::draw::height(),
+    }
+}
+
+fn value_text_baseline<V: WidgetExt + ValueText>(widget: &V) -> i32 {
+    fltk::draw::set_font(widget.value_text_font(), widget.value_text_size());
+    widget.frame().dy() + fltk::draw::height() - fltk::draw::descent()
+}
+
+fn max_value_width(minimum: f64, maximum: f64, step: f64) -> i32 {
+    let precision = value_precision(step);
+    [minimum, maximum]
+        .iter()
+        .map(|value| fltk::draw::width(&format_value(*value, precision)) as i32)
+        .max()
+        .unwrap_or_default()
+}
+
+fn value_precision(step: f64) -> Option<usize> {
+    if step <= 0.0 {
+        return None;
+    }
+
+    let mut scaled = step;
+    let mut precision = 0;
+    while (scaled - scaled.round()).abs() > 1e-9 && precision < MAX_VALUE_PRECISION {
+        scaled *= 10.0;
+        precision += 1;
+    }
+    Some(precision)
+}
+
+fn format_value(value: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => format!("{:.*}", precision, value),
+        None => format!("{}", value),
+    }
+}
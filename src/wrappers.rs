@@ -2,9 +2,39 @@ use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::rc::Rc;
 
+use fltk::browser::{Browser, FileBrowser, HoldBrowser, MultiBrowser, SelectBrowser};
+use fltk::button::{
+    Button, CheckButton, LightButton, RadioButton, RadioLightButton, RadioRoundButton,
+    RepeatButton, ReturnButton, RoundButton, ShortcutButton, ToggleButton,
+};
+use fltk::frame::Frame;
+use fltk::input::{FileInput, FloatInput, Input, IntInput, MultilineInput, SecretInput};
+use fltk::menu::{Choice, MenuButton};
+use fltk::misc::{InputChoice, Spinner};
+use fltk::output::{MultilineOutput, Output};
 use fltk::prelude::*;
+use fltk::table::{Table, TableRow};
+use fltk::text::{TextDisplay, TextEditor};
+use fltk::tree::Tree;
+use fltk::valuator::{
+    Counter, Dial, FillDial, FillSlider, HorFillSlider, HorNiceSlider, HorSlider, HorValueSlider,
+    LineDial, NiceSlider, Roller, Scrollbar, SimpleCounter, Slider, ValueInput, ValueOutput,
+    ValueSlider,
+};
 use fltk::widget::Widget;
 
+use super::browser::BrowserElement;
+use super::button::{ButtonElement, CheckButtonElement, LightButtonElement, MenuButtonElement};
+use super::frame::FrameElement;
+use super::input::InputElement;
+use super::misc::{ChoiceElement, InputChoiceElement};
+use super::table::TableElement;
+use super::text::TextElement;
+use super::tree::TreeElement;
+use super::valuator::{
+    CounterElement, DialElement, SliderElement, SpinnerElement, ValueInputElement,
+    ValueSliderElement,
+};
 use super::{IntoWidget, LayoutElement, LayoutWidgetWrapper, Size};
 
 pub struct SimpleWrapper {
@@ -17,6 +47,10 @@ impl LayoutElement for SimpleWrapper {
         self.min_size
     }
 
+    fn widget(&self) -> Option<Widget> {
+        Some(self.widget.clone())
+    }
+
     fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
         self.widget.clone().resize(x, y, width, height);
     }
@@ -42,9 +76,18 @@ impl SimpleWrapper {
     }
 }
 
+type CatchAll = Box<dyn Fn(Widget) -> Rc<dyn LayoutElement>>;
+
 pub struct WrapperFactory {
     map: HashMap<TypeId, Box<dyn Any>>,
-    catch_all: Box<dyn Fn(Widget) -> Rc<dyn LayoutElement>>,
+    catch_all: Option<CatchAll>,
+    parent: Option<Rc<WrapperFactory>>,
+}
+
+impl Default for WrapperFactory {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl WrapperFactory {
@@ -52,36 +95,123 @@ impl WrapperFactory {
         Self::with_catch_all(|widget| Rc::new(SimpleWrapper::wrap(widget)))
     }
 
+    pub fn with_defaults() -> Self {
+        let mut factory = Self::new();
+
+        factory.set_wrapper::<Button, ButtonElement<Button>>();
+        factory.set_wrapper::<CheckButton, CheckButtonElement<CheckButton>>();
+        factory.set_wrapper::<LightButton, LightButtonElement<LightButton>>();
+        factory.set_wrapper::<RadioButton, ButtonElement<RadioButton>>();
+        factory.set_wrapper::<RadioLightButton, LightButtonElement<RadioLightButton>>();
+        factory.set_wrapper::<RadioRoundButton, CheckButtonElement<RadioRoundButton>>();
+        factory.set_wrapper::<RepeatButton, ButtonElement<RepeatButton>>();
+        factory.set_wrapper::<ReturnButton, ButtonElement<ReturnButton>>();
+        factory.set_wrapper::<RoundButton, CheckButtonElement<RoundButton>>();
+        factory.set_wrapper::<ShortcutButton, ButtonElement<ShortcutButton>>();
+        factory.set_wrapper::<ToggleButton, ButtonElement<ToggleButton>>();
+        factory.set_wrapper::<MenuButton, MenuButtonElement>();
+        factory.set_wrapper::<Choice, ChoiceElement>();
+
+        factory.set_wrapper::<Frame, FrameElement>();
+
+        factory.set_wrapper::<Input, InputElement<Input>>();
+        factory.set_wrapper::<IntInput, InputElement<IntInput>>();
+        factory.set_wrapper::<FloatInput, InputElement<FloatInput>>();
+        factory.set_wrapper::<MultilineInput, InputElement<MultilineInput>>();
+        factory.set_wrapper::<FileInput, InputElement<FileInput>>();
+        factory.set_wrapper::<SecretInput, InputElement<SecretInput>>();
+        factory.set_wrapper::<Output, InputElement<Output>>();
+        factory.set_wrapper::<MultilineOutput, InputElement<MultilineOutput>>();
+        factory.set_wrapper::<InputChoice, InputChoiceElement>();
+
+        factory.set_wrapper::<TextDisplay, TextElement<TextDisplay>>();
+        factory.set_wrapper::<TextEditor, TextElement<TextEditor>>();
+
+        factory.set_wrapper::<Browser, BrowserElement<Browser>>();
+        factory.set_wrapper::<SelectBrowser, BrowserElement<SelectBrowser>>();
+        factory.set_wrapper::<MultiBrowser, BrowserElement<MultiBrowser>>();
+        factory.set_wrapper::<HoldBrowser, BrowserElement<HoldBrowser>>();
+        factory.set_wrapper::<FileBrowser, BrowserElement<FileBrowser>>();
+        factory.set_wrapper::<Tree, TreeElement>();
+        factory.set_wrapper::<Table, TableElement<Table>>();
+        factory.set_wrapper::<TableRow, TableElement<TableRow>>();
+
+        factory.set_wrapper::<Slider, SliderElement<Slider>>();
+        factory.set_wrapper::<NiceSlider, SliderElement<NiceSlider>>();
+        factory.set_wrapper::<FillSlider, SliderElement<FillSlider>>();
+        factory.set_wrapper::<HorSlider, SliderElement<HorSlider>>();
+        factory.set_wrapper::<HorNiceSlider, SliderElement<HorNiceSlider>>();
+        factory.set_wrapper::<HorFillSlider, SliderElement<HorFillSlider>>();
+        factory.set_wrapper::<Scrollbar, SliderElement<Scrollbar>>();
+        factory.set_wrapper::<Roller, SliderElement<Roller>>();
+        factory.set_wrapper::<ValueSlider, ValueSliderElement<ValueSlider>>();
+        factory.set_wrapper::<HorValueSlider, ValueSliderElement<HorValueSlider>>();
+        factory.set_wrapper::<ValueInput, ValueInputElement<ValueInput>>();
+        factory.set_wrapper::<ValueOutput, ValueInputElement<ValueOutput>>();
+        factory.set_wrapper::<Counter, CounterElement<Counter>>();
+        factory.set_wrapper::<SimpleCounter, CounterElement<SimpleCounter>>();
+        factory.set_wrapper::<Spinner, SpinnerElement>();
+        factory.set_wrapper::<Dial, DialElement<Dial>>();
+        factory.set_wrapper::<FillDial, DialElement<FillDial>>();
+        factory.set_wrapper::<LineDial, DialElement<LineDial>>();
+
+        factory
+    }
+
     pub fn with_catch_all(catch_all: impl Fn(Widget) -> Rc<dyn LayoutElement> + 'static) -> Self {
         Self {
             map: HashMap::new(),
-            catch_all: Box::new(catch_all),
+            catch_all: Some(Box::new(catch_all)),
+            parent: None,
+        }
+    }
+
+    pub fn with_parent(parent: Rc<WrapperFactory>) -> Self {
+        Self {
+            map: HashMap::new(),
+            catch_all: None,
+            parent: Some(parent),
         }
     }
 
     pub fn set_wrapper<W: IntoWidget + 'static, L: LayoutWidgetWrapper<W> + 'static>(&mut self) {
+        self.set_wrapper_fn::<W>(|widget| Rc::new(L::wrap(widget)));
+    }
+
+    pub fn set_wrapper_fn<W: IntoWidget + 'static>(
+        &mut self,
+        wrapper: impl Fn(W) -> Rc<dyn LayoutElement> + 'static,
+    ) {
         self.map
-            .insert(TypeId::of::<W>(), Box::new(Factory::<W>::new::<L>()));
+            .insert(TypeId::of::<W>(), Box::new(Factory::<W>(Box::new(wrapper))));
+    }
+
+    pub fn set_catch_all(&mut self, catch_all: impl Fn(Widget) -> Rc<dyn LayoutElement> + 'static) {
+        self.catch_all = Some(Box::new(catch_all));
     }
 
     pub fn wrap<W: IntoWidget + 'static>(&self, widget: W) -> Rc<dyn LayoutElement> {
         match self.factory_for::<W>() {
             Some(factory) => (factory.0)(widget),
-            None => (self.catch_all)(widget.into_widget()),
+            None => self.wrap_catch_all(widget.into_widget()),
         }
     }
 
     fn factory_for<W: IntoWidget + 'static>(&self) -> Option<&Factory<W>> {
-        let erased = self.map.get(&TypeId::of::<W>())?;
-        let erased = &*erased;
+        let erased = match self.map.get(&TypeId::of::<W>()) {
+            Some(erased) => erased,
+            None => return self.parent.as_ref()?.factory_for::<W>(),
+        };
         erased.downcast_ref::<Factory<W>>()
     }
-}
 
-struct Factory<W: IntoWidget + 'static>(fn(W) -> Rc<dyn LayoutElement>);
-
-impl<W: IntoWidget + 'static> Factory<W> {
-    fn new<L: LayoutWidgetWrapper<W> + 'static>() -> Self {
-        Self(|widget| Rc::new(L::wrap(widget)))
+    fn wrap_catch_all(&self, widget: Widget) -> Rc<dyn LayoutElement> {
+        match (&self.catch_all, &self.parent) {
+            (Some(catch_all), _) => catch_all(widget),
+            (None, Some(parent)) => parent.wrap_catch_all(widget),
+            (None, None) => Rc::new(SimpleWrapper::wrap(widget)),
+        }
     }
 }
+
+struct Factory<W: IntoWidget + 'static>(Box<dyn Fn(W) -> Rc<dyn LayoutElement>>);